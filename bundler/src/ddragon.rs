@@ -0,0 +1,287 @@
+//! Reads the JSON files and images of an extracted Data Dragon tarball.
+//!
+//! ```text
+//! dragontail/
+//! ├── img/perk-images/...          rune icons
+//! └── 15.13.1/
+//!     ├── data/en_US/*.json
+//!     └── img/{champion,item,spell}/*.png
+//! ```
+use crate::Error;
+
+use aery_core::assets::{Image, champion, item, rune, spell};
+use serde::Deserialize;
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Deserialize)]
+struct Data<T> {
+    data: HashMap<String, T>,
+}
+
+#[derive(Deserialize)]
+struct ImageRef {
+    full: String,
+}
+
+#[derive(Deserialize)]
+struct Champion {
+    key: String,
+    name: String,
+    title: String,
+    blurb: String,
+    tags: Vec<String>,
+    image: ImageRef,
+}
+
+#[derive(Deserialize)]
+struct Spell {
+    key: String,
+    name: String,
+    description: String,
+    cooldown: Vec<f32>,
+    image: ImageRef,
+}
+
+#[derive(Deserialize)]
+struct Item {
+    name: String,
+    description: String,
+    plaintext: String,
+    gold: Gold,
+    #[serde(default)]
+    from: Vec<String>,
+    #[serde(default)]
+    into: Vec<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    image: ImageRef,
+}
+
+#[derive(Deserialize)]
+struct Gold {
+    base: u32,
+    total: u32,
+    sell: u32,
+}
+
+#[derive(Deserialize)]
+struct RunePath {
+    id: u16,
+    name: String,
+    icon: String,
+    slots: Vec<Slot>,
+}
+
+#[derive(Deserialize)]
+struct Slot {
+    runes: Vec<Rune>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Rune {
+    id: u16,
+    name: String,
+    short_desc: String,
+    icon: String,
+}
+
+pub struct DataDragon {
+    root: PathBuf,
+    version: String,
+    locale: String,
+}
+
+impl DataDragon {
+    /// Opens the tarball at `root`, finding its version from the versioned directory inside it.
+    pub fn open(root: impl Into<PathBuf>, locale: impl ToString) -> Result<Self, Error> {
+        let root = root.into();
+        let entries = fs::read_dir(&root).map_err(|source| Error::io(&root, source))?;
+
+        let version = entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().join("data").is_dir())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .filter(|name| name.starts_with(|c: char| c.is_ascii_digit()))
+            .max_by(|a, b| aery_core::assets::compare_versions(a, b))
+            .ok_or_else(|| Error::MissingVersion(root.clone()))?;
+
+        Ok(Self {
+            root,
+            version,
+            locale: locale.to_string(),
+        })
+    }
+
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    pub fn champions(&self) -> Result<champion::AssetMap, Error> {
+        let champions: Data<Champion> = self.data("champion.json")?;
+
+        let map = champions
+            .data
+            .into_values()
+            .map(|champion| {
+                let id = champion::Id::from_key(&champion.key)
+                    .ok_or_else(|| Error::invalid_key("champion", &champion.key))?;
+                let icon = self.image("champion", &champion.image)?;
+                let data = champion::Data {
+                    name: champion.name,
+                    title: champion.title,
+                    description: champion.blurb,
+                    tags: champion.tags,
+                };
+
+                Ok((id, champion::Assets { icon, data }))
+            })
+            .collect::<Result<_, Error>>()?;
+
+        Ok(champion::AssetMap(map))
+    }
+
+    pub fn spells(&self) -> Result<spell::AssetMap, Error> {
+        let spells: Data<Spell> = self.data("summoner.json")?;
+
+        let map = spells
+            .data
+            .into_values()
+            .map(|spell| {
+                let id = spell::Id::from_key(&spell.key)
+                    .ok_or_else(|| Error::invalid_key("spell", &spell.key))?;
+                let icon = self.image("spell", &spell.image)?;
+
+                let data = spell::Data {
+                    name: spell.name,
+                    description: strip_markup(&spell.description),
+                    cooldown: spell.cooldown.first().copied().unwrap_or_default(),
+                };
+
+                Ok((id, spell::Assets { icon, data }))
+            })
+            .collect::<Result<_, Error>>()?;
+
+        Ok(spell::AssetMap(map))
+    }
+
+    pub fn items(&self) -> Result<item::AssetMap, Error> {
+        let items: Data<Item> = self.data("item.json")?;
+
+        let map = items
+            .data
+            .into_iter()
+            .map(|(key, item)| {
+                let item_id = |key: &String| {
+                    item::Id::from_key(key).ok_or_else(|| Error::invalid_key("item", key))
+                };
+                let id = item_id(&key)?;
+                let icon = self.image("item", &item.image)?;
+
+                let data = item::Data {
+                    name: item.name,
+                    description: strip_markup(&item.description),
+                    plaintext: item.plaintext,
+                    gold: item::Gold {
+                        base: item.gold.base,
+                        total: item.gold.total,
+                        sell: item.gold.sell,
+                    },
+                    from: item.from.iter().map(item_id).collect::<Result<_, _>>()?,
+                    into: item.into.iter().map(item_id).collect::<Result<_, _>>()?,
+                    tags: item.tags,
+                };
+
+                Ok((id, item::Assets { icon, data }))
+            })
+            .collect::<Result<_, Error>>()?;
+
+        Ok(item::AssetMap(map))
+    }
+
+    /// Returns the icons of both rune paths and the runes inside them.
+    pub fn runes(&self) -> Result<rune::AssetMap, Error> {
+        let paths: Vec<RunePath> = self.data("runesReforged.json")?;
+        let mut map = BTreeMap::new();
+
+        for path in paths {
+            let data = rune::Data {
+                name: path.name,
+                description: String::new(),
+            };
+            map.insert(rune::Id(path.id), self.rune(&path.icon, data)?);
+
+            for rune in path.slots.into_iter().flat_map(|slot| slot.runes) {
+                let data = rune::Data {
+                    name: rune.name,
+                    description: strip_markup(&rune.short_desc),
+                };
+                map.insert(rune::Id(rune.id), self.rune(&rune.icon, data)?);
+            }
+        }
+
+        Ok(rune::AssetMap(map))
+    }
+
+    fn rune(&self, icon: &str, data: rune::Data) -> Result<rune::Assets, Error> {
+        let icon = read(&self.root.join("img").join(icon))?;
+
+        Ok(rune::Assets { icon, data })
+    }
+
+    fn image(&self, kind: &str, image: &ImageRef) -> Result<Image, Error> {
+        read(
+            &self
+                .root
+                .join(&self.version)
+                .join("img")
+                .join(kind)
+                .join(&image.full),
+        )
+    }
+
+    fn data<T: serde::de::DeserializeOwned>(&self, file: &str) -> Result<T, Error> {
+        let path = self
+            .root
+            .join(&self.version)
+            .join("data")
+            .join(&self.locale)
+            .join(file);
+        let bytes = fs::read(&path).map_err(|source| Error::io(&path, source))?;
+
+        serde_json::from_slice(&bytes).map_err(|source| Error::Json { path, source })
+    }
+}
+
+pub fn read(path: &Path) -> Result<Image, Error> {
+    fs::read(path)
+        .map(Image::from)
+        .map_err(|source| Error::io(path, source))
+}
+
+/// Removes the HTML-like markup of Data Dragon descriptions, keeping line breaks.
+fn strip_markup(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('<') {
+        stripped.push_str(&rest[..start]);
+
+        let Some(end) = rest[start..].find('>') else {
+            rest = &rest[start..];
+            break;
+        };
+
+        if rest[start + 1..start + end].trim_end_matches('/').trim() == "br" {
+            stripped.push('\n');
+        }
+
+        rest = &rest[start + end + 1..];
+    }
+
+    stripped.push_str(rest);
+    stripped.trim().to_string()
+}
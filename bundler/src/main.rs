@@ -0,0 +1,205 @@
+//! Builds an asset bundle from an extracted Data Dragon tarball and the ranked emblem images.
+//!
+//! ```text
+//! aery_bundler <dragontail> <emblems> <output> [--locale en_US]
+//! aery_bundler delta <old.aery> <new.aery> <output>
+//! ```
+//!
+//! The emblems directory must contain one `{tier}.png` per tier (e.g. `grandmaster.png`).
+//!
+//! `delta` writes the changes between two bundles, which clients on the old version can apply
+//! instead of downloading the whole new bundle.
+mod ddragon;
+use ddragon::DataDragon;
+
+use aery_core as core;
+use core::Assets;
+use core::assets::{Delta, emblem};
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+const USAGE: &str = "usage: aery_bundler <dragontail> <emblems> <output> [--locale en_US]
+       aery_bundler delta <old.aery> <new.aery> <output>";
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("failed to access `{}`: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("failed to parse `{}`: {source}", path.display())]
+    Json {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+    #[error("invalid {kind} key `{key}`")]
+    InvalidKey { kind: &'static str, key: String },
+    #[error("no Data Dragon version found in `{}`", .0.display())]
+    MissingVersion(PathBuf),
+    #[error("invalid bundle `{}`: {source}", path.display())]
+    Decode {
+        path: PathBuf,
+        source: core::DecodeError,
+    },
+}
+
+impl Error {
+    fn io(path: &Path, source: std::io::Error) -> Self {
+        Self::Io {
+            path: path.to_path_buf(),
+            source,
+        }
+    }
+
+    fn invalid_key(kind: &'static str, key: &str) -> Self {
+        Self::InvalidKey {
+            kind,
+            key: key.to_owned(),
+        }
+    }
+}
+
+enum Command {
+    Bundle(Arguments),
+    Delta {
+        old: PathBuf,
+        new: PathBuf,
+        output: PathBuf,
+    },
+}
+
+impl Command {
+    fn parse(mut args: impl Iterator<Item = String>) -> Option<Self> {
+        let first = args.next()?;
+
+        if first == "delta" {
+            let [old, new, output] =
+                <[PathBuf; 3]>::try_from(args.map(PathBuf::from).collect::<Vec<_>>()).ok()?;
+
+            return Some(Self::Delta { old, new, output });
+        }
+
+        Arguments::parse(std::iter::once(first).chain(args)).map(Self::Bundle)
+    }
+}
+
+struct Arguments {
+    dragontail: PathBuf,
+    emblems: PathBuf,
+    output: PathBuf,
+    locale: String,
+}
+
+impl Arguments {
+    fn parse(mut args: impl Iterator<Item = String>) -> Option<Self> {
+        let mut paths = Vec::new();
+        let mut locale = String::from("en_US");
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--locale" => locale = args.next()?,
+                _ => paths.push(PathBuf::from(arg)),
+            }
+        }
+
+        let [dragontail, emblems, output] = <[PathBuf; 3]>::try_from(paths).ok()?;
+
+        Some(Self {
+            dragontail,
+            emblems,
+            output,
+            locale,
+        })
+    }
+}
+
+fn main() -> ExitCode {
+    let Some(command) = Command::parse(std::env::args().skip(1)) else {
+        eprintln!("{USAGE}");
+        return ExitCode::FAILURE;
+    };
+
+    let result = match command {
+        Command::Bundle(arguments) => bundle(&arguments).map(|assets| {
+            format!(
+                "Bundled {} champions, {} runes, {} spells, {} items and {} emblems ({}) into `{}`",
+                assets.champion.0.len(),
+                assets.rune.0.len(),
+                assets.spell.0.len(),
+                assets.item.0.len(),
+                assets.emblem.0.len(),
+                assets.version,
+                arguments.output.display(),
+            )
+        }),
+        Command::Delta { old, new, output } => delta(&old, &new, &output).map(|delta| {
+            format!(
+                "Wrote the delta from {} to {} into `{}`",
+                delta.from,
+                delta.to,
+                output.display()
+            )
+        }),
+    };
+
+    match result {
+        Ok(summary) => {
+            println!("{summary}");
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("{error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn bundle(arguments: &Arguments) -> Result<Assets, Error> {
+    let ddragon = DataDragon::open(&arguments.dragontail, &arguments.locale)?;
+
+    let assets = Assets {
+        version: ddragon.version().to_string(),
+        champion: ddragon.champions()?,
+        rune: ddragon.runes()?,
+        spell: ddragon.spells()?,
+        item: ddragon.items()?,
+        emblem: emblems(&arguments.emblems)?,
+    };
+
+    std::fs::write(&arguments.output, assets.encode())
+        .map_err(|source| Error::io(&arguments.output, source))?;
+
+    Ok(assets)
+}
+
+fn delta(old: &Path, new: &Path, output: &Path) -> Result<Delta, Error> {
+    let delta = Delta::between(&read_bundle(old)?, read_bundle(new)?);
+
+    std::fs::write(output, delta.encode()).map_err(|source| Error::io(output, source))?;
+
+    Ok(delta)
+}
+
+fn read_bundle(path: &Path) -> Result<Assets, Error> {
+    let bytes = std::fs::read(path).map_err(|source| Error::io(path, source))?;
+
+    Assets::decode(bytes).map_err(|source| Error::Decode {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+fn emblems(directory: &Path) -> Result<emblem::AssetMap, Error> {
+    let map = emblem::Id::ALL
+        .iter()
+        .map(|id| {
+            let icon = ddragon::read(&directory.join(format!("{}.png", id.into_key())))?;
+
+            Ok((*id, emblem::Assets { icon }))
+        })
+        .collect::<Result<_, Error>>()?;
+
+    Ok(emblem::AssetMap(map))
+}
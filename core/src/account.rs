@@ -1,3 +1,4 @@
+use crate::{Client, ParseError, Region, RequestError};
 use riven::models::account_v1;
 use std::fmt;
 
@@ -16,6 +17,18 @@ impl RiotId {
     }
 }
 
+impl std::str::FromStr for RiotId {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        value
+            .rsplit_once('#')
+            .filter(|(name, tagline)| !name.is_empty() && !tagline.is_empty())
+            .map(|(name, tagline)| RiotId::new(name, tagline))
+            .ok_or_else(|| ParseError::RiotId(value.to_string()))
+    }
+}
+
 impl fmt::Display for RiotId {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let name = self.name.as_deref().unwrap_or("missing");
@@ -40,6 +53,25 @@ pub struct Account {
 }
 
 impl Account {
+    pub async fn fetch(
+        client: &Client,
+        region: Region,
+        riot_id: &RiotId,
+    ) -> Result<Self, RequestError> {
+        let (Some(name), Some(tagline)) = (&riot_id.name, &riot_id.tagline) else {
+            return Err(RequestError::NotFound);
+        };
+
+        client
+            .as_ref()
+            .account_v1()
+            .get_by_riot_id(region.into(), name, tagline)
+            .await
+            .map_err(RequestError::internal)?
+            .map(Account::from)
+            .ok_or(RequestError::NotFound)
+    }
+
     #[cfg(feature = "dummy")]
     pub fn dummy(riot_id: RiotId) -> Self {
         let name = riot_id.name.as_deref().unwrap_or("foo");
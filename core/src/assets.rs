@@ -1,12 +1,18 @@
+use crate::codec::{self, DecodeError, Payload};
 use bytes::Bytes;
 
+pub use delta::Delta;
+pub use index::{Icon, Index};
+
 pub mod champion;
+pub mod delta;
 pub mod emblem;
+pub mod index;
 pub mod item;
 pub mod rune;
 pub mod spell;
 
-#[derive(Clone, bitcode::Encode, bitcode::Decode)]
+#[derive(Clone, PartialEq, bitcode::Encode, bitcode::Decode)]
 pub struct Image(Vec<u8>);
 
 impl AsRef<[u8]> for Image {
@@ -41,6 +47,8 @@ impl std::fmt::Debug for Image {
 
 #[derive(bitcode::Encode, bitcode::Decode)]
 pub struct Assets {
+    /// The Data Dragon version the assets were built from (e.g. `15.13.1`).
+    pub version: String,
     pub champion: champion::AssetMap,
     pub rune: rune::AssetMap,
     pub spell: spell::AssetMap,
@@ -50,12 +58,48 @@ pub struct Assets {
 
 impl Assets {
     pub fn encode(&self) -> Vec<u8> {
-        let bytes = bitcode::encode(self);
-        lz4_flex::compress_prepend_size(&bytes)
+        codec::encode(self)
+    }
+
+    pub fn decode(bytes: Vec<u8>) -> Result<Self, DecodeError> {
+        codec::decode(&bytes)
+    }
+
+    pub fn champion(&self, id: champion::Id) -> Option<&champion::Data> {
+        self.champion.0.get(&id).map(|assets| &assets.data)
+    }
+
+    pub fn rune(&self, id: rune::Id) -> Option<&rune::Data> {
+        self.rune.0.get(&id).map(|assets| &assets.data)
+    }
+
+    pub fn spell(&self, id: spell::Id) -> Option<&spell::Data> {
+        self.spell.0.get(&id).map(|assets| &assets.data)
     }
 
-    pub fn decode(bytes: Vec<u8>) -> Self {
-        let decompressed = lz4_flex::decompress_size_prepended(&bytes).unwrap();
-        bitcode::decode(&decompressed).unwrap()
+    pub fn item(&self, id: item::Id) -> Option<&item::Data> {
+        self.item.0.get(&id).map(|assets| &assets.data)
     }
+
+    /// Whether these assets were built from an older Data Dragon version than `version`.
+    pub fn is_outdated(&self, version: &str) -> bool {
+        compare_versions(&self.version, version).is_lt()
+    }
+}
+
+/// Compares two Data Dragon versions (e.g. `15.13.1`) part by part.
+pub fn compare_versions(a: &str, b: &str) -> std::cmp::Ordering {
+    fn parse(version: &str) -> Vec<u32> {
+        version
+            .split('.')
+            .map(|part| part.parse().unwrap_or(0))
+            .collect()
+    }
+
+    parse(a).cmp(&parse(b))
+}
+
+impl Payload for Assets {
+    const KIND: codec::Kind = codec::Kind::Assets;
+    const VERSION: u16 = 3;
 }
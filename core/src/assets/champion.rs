@@ -1,19 +1,35 @@
 use crate::assets;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
-#[derive(bitcode::Encode, bitcode::Decode, PartialEq, Eq, Hash, Clone, Copy)]
+#[derive(
+    bitcode::Encode, bitcode::Decode, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy,
+)]
 pub struct Id(pub u16);
 
 impl Id {
-    pub fn from_key(key: &str) -> Self {
-        Self(key.parse().unwrap())
+    /// The id of a Data Dragon `key`, which is its number as a string.
+    pub fn from_key(key: &str) -> Option<Self> {
+        key.parse().ok().map(Self)
     }
 }
 
-#[derive(bitcode::Encode, bitcode::Decode)]
+#[derive(bitcode::Encode, bitcode::Decode, PartialEq)]
 pub struct Assets {
     pub icon: assets::Image,
+    pub data: Data,
+}
+
+/// Static data of a champion.
+#[derive(bitcode::Encode, bitcode::Decode, Debug, Clone, PartialEq)]
+pub struct Data {
+    pub name: String,
+    /// The title shown after the name (e.g. `the Darkin Blade`).
+    pub title: String,
+    /// A short lore description.
+    pub description: String,
+    /// The classes of the champion (e.g. `Fighter`, `Tank`).
+    pub tags: Vec<String>,
 }
 
 #[derive(bitcode::Encode, bitcode::Decode)]
-pub struct AssetMap(pub HashMap<Id, Assets>);
+pub struct AssetMap(pub BTreeMap<Id, Assets>);
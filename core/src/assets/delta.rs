@@ -0,0 +1,118 @@
+//! The changes between two versions of [`Assets`], so patch day updates only carry the entries
+//! that were added, changed or removed.
+use crate::assets::{Assets, champion, emblem, item, rune, spell};
+use crate::codec::{self, DecodeError, Payload};
+
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ApplyError {
+    #[error("delta applies to version {expected}, but the assets are version {found}")]
+    VersionMismatch { expected: String, found: String },
+}
+
+/// The added or changed entries of an `AssetMap` and the ids of the removed ones.
+#[derive(bitcode::Encode, bitcode::Decode)]
+pub struct Changes<Id: Ord, T> {
+    pub upserted: BTreeMap<Id, T>,
+    pub removed: Vec<Id>,
+}
+
+impl<Id: Ord + Copy, T: PartialEq> Changes<Id, T> {
+    fn between(old: &BTreeMap<Id, T>, new: BTreeMap<Id, T>) -> Self {
+        let removed = old
+            .keys()
+            .filter(|id| !new.contains_key(id))
+            .copied()
+            .collect();
+        let upserted = new
+            .into_iter()
+            .filter(|(id, entry)| old.get(id) != Some(entry))
+            .collect();
+
+        Self { upserted, removed }
+    }
+
+    fn apply(self, map: &mut BTreeMap<Id, T>) {
+        for id in &self.removed {
+            map.remove(id);
+        }
+
+        map.extend(self.upserted);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.upserted.is_empty() && self.removed.is_empty()
+    }
+}
+
+#[derive(bitcode::Encode, bitcode::Decode)]
+pub struct Delta {
+    /// The version the delta applies to.
+    pub from: String,
+    /// The version of the assets once the delta is applied.
+    pub to: String,
+    pub champion: Changes<champion::Id, champion::Assets>,
+    pub rune: Changes<rune::Id, rune::Assets>,
+    pub spell: Changes<spell::Id, spell::Assets>,
+    pub item: Changes<item::Id, item::Assets>,
+    pub emblem: Changes<emblem::Id, emblem::Assets>,
+}
+
+impl Delta {
+    /// Computes the changes needed to turn `old` into `new`.
+    pub fn between(old: &Assets, new: Assets) -> Self {
+        Self {
+            from: old.version.clone(),
+            to: new.version,
+            champion: Changes::between(&old.champion.0, new.champion.0),
+            rune: Changes::between(&old.rune.0, new.rune.0),
+            spell: Changes::between(&old.spell.0, new.spell.0),
+            item: Changes::between(&old.item.0, new.item.0),
+            emblem: Changes::between(&old.emblem.0, new.emblem.0),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.champion.is_empty()
+            && self.rune.is_empty()
+            && self.spell.is_empty()
+            && self.item.is_empty()
+            && self.emblem.is_empty()
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        codec::encode(self)
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self, DecodeError> {
+        codec::decode(bytes)
+    }
+}
+
+impl Payload for Delta {
+    const KIND: codec::Kind = codec::Kind::AssetsDelta;
+    /// Entries are encoded as they are in [`Assets`], so bump it alongside the assets version.
+    const VERSION: u16 = 1;
+}
+
+impl Assets {
+    /// Applies a [`Delta`] computed from these assets, updating them to its target version.
+    pub fn apply(&mut self, delta: Delta) -> Result<(), ApplyError> {
+        if delta.from != self.version {
+            return Err(ApplyError::VersionMismatch {
+                expected: delta.from,
+                found: self.version.clone(),
+            });
+        }
+
+        delta.champion.apply(&mut self.champion.0);
+        delta.rune.apply(&mut self.rune.0);
+        delta.spell.apply(&mut self.spell.0);
+        delta.item.apply(&mut self.item.0);
+        delta.emblem.apply(&mut self.emblem.0);
+        self.version = delta.to;
+
+        Ok(())
+    }
+}
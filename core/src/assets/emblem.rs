@@ -1,7 +1,9 @@
 use crate::{assets, summoner};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
-#[derive(bitcode::Encode, bitcode::Decode, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[derive(
+    bitcode::Encode, bitcode::Decode, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy,
+)]
 pub struct Id(pub u8);
 
 impl Id {
@@ -70,10 +72,10 @@ impl Id {
     }
 }
 
-#[derive(bitcode::Encode, bitcode::Decode)]
+#[derive(bitcode::Encode, bitcode::Decode, PartialEq)]
 pub struct Assets {
     pub icon: assets::Image,
 }
 
 #[derive(bitcode::Encode, bitcode::Decode)]
-pub struct AssetMap(pub HashMap<Id, Assets>);
+pub struct AssetMap(pub BTreeMap<Id, Assets>);
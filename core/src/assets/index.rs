@@ -0,0 +1,240 @@
+//! An [`Index`] of the assets, so icons can be loaded one by one instead of all at once.
+//!
+//! The index carries the static data of every entry and the location of its icon inside a
+//! separate blob of concatenated icons. Emblems are small and always needed, so they are
+//! kept inline.
+use crate::assets::{Assets, Image, champion, emblem, item, rune, spell};
+use crate::codec::{self, DecodeError, Payload};
+
+use std::collections::BTreeMap;
+
+/// An icon that can be loaded on demand.
+#[derive(
+    bitcode::Encode, bitcode::Decode, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy,
+)]
+pub enum Icon {
+    Champion(champion::Id),
+    Rune(rune::Id),
+    Spell(spell::Id),
+    Item(item::Id),
+}
+
+impl Icon {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Icon::Champion(_) => "champion",
+            Icon::Rune(_) => "rune",
+            Icon::Spell(_) => "spell",
+            Icon::Item(_) => "item",
+        }
+    }
+
+    pub fn id(&self) -> u32 {
+        match self {
+            Icon::Champion(id) => id.0 as u32,
+            Icon::Rune(id) => id.0 as u32,
+            Icon::Spell(id) => id.0,
+            Icon::Item(id) => id.0,
+        }
+    }
+
+    /// The inverse of [`Icon::kind`] and [`Icon::id`].
+    pub fn from_parts(kind: &str, id: u32) -> Option<Self> {
+        let icon = match kind {
+            "champion" => Icon::Champion(champion::Id(id.try_into().ok()?)),
+            "rune" => Icon::Rune(rune::Id(id.try_into().ok()?)),
+            "spell" => Icon::Spell(spell::Id(id)),
+            "item" => Icon::Item(item::Id(id)),
+            _ => return None,
+        };
+
+        Some(icon)
+    }
+}
+
+/// Where an icon is inside the blob of icons.
+#[derive(bitcode::Encode, bitcode::Decode, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    pub offset: u32,
+    pub length: u32,
+}
+
+impl Location {
+    pub fn get<'a>(&self, icons: &'a [u8]) -> Option<&'a [u8]> {
+        let start = self.offset as usize;
+
+        icons.get(start..start + self.length as usize)
+    }
+}
+
+#[derive(bitcode::Encode, bitcode::Decode)]
+pub struct Index {
+    pub version: String,
+    pub champion: BTreeMap<champion::Id, champion::Data>,
+    pub rune: BTreeMap<rune::Id, rune::Data>,
+    pub spell: BTreeMap<spell::Id, spell::Data>,
+    pub item: BTreeMap<item::Id, item::Data>,
+    pub emblem: emblem::AssetMap,
+    pub icons: BTreeMap<Icon, Location>,
+}
+
+impl Index {
+    pub fn encode(&self) -> Vec<u8> {
+        codec::encode(self)
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self, DecodeError> {
+        codec::decode(bytes)
+    }
+
+    /// Rebuilds the full assets from the index and its blob of icons.
+    pub fn into_assets(self, icons: &[u8]) -> Option<Assets> {
+        let icon = |icon| {
+            self.icons
+                .get(&icon)
+                .and_then(|location| location.get(icons))
+                .map(|bytes| Image::from(bytes.to_vec()))
+        };
+
+        let champion = self
+            .champion
+            .iter()
+            .map(|(id, data)| {
+                let icon = icon(Icon::Champion(*id))?;
+
+                Some((
+                    *id,
+                    champion::Assets {
+                        icon,
+                        data: data.clone(),
+                    },
+                ))
+            })
+            .collect::<Option<_>>()?;
+        let rune = self
+            .rune
+            .iter()
+            .map(|(id, data)| {
+                let icon = icon(Icon::Rune(*id))?;
+
+                Some((
+                    *id,
+                    rune::Assets {
+                        icon,
+                        data: data.clone(),
+                    },
+                ))
+            })
+            .collect::<Option<_>>()?;
+        let spell = self
+            .spell
+            .iter()
+            .map(|(id, data)| {
+                let icon = icon(Icon::Spell(*id))?;
+
+                Some((
+                    *id,
+                    spell::Assets {
+                        icon,
+                        data: data.clone(),
+                    },
+                ))
+            })
+            .collect::<Option<_>>()?;
+        let item = self
+            .item
+            .iter()
+            .map(|(id, data)| {
+                let icon = icon(Icon::Item(*id))?;
+
+                Some((
+                    *id,
+                    item::Assets {
+                        icon,
+                        data: data.clone(),
+                    },
+                ))
+            })
+            .collect::<Option<_>>()?;
+
+        Some(Assets {
+            version: self.version,
+            champion: champion::AssetMap(champion),
+            rune: rune::AssetMap(rune),
+            spell: spell::AssetMap(spell),
+            item: item::AssetMap(item),
+            emblem: self.emblem,
+        })
+    }
+}
+
+impl Payload for Index {
+    const KIND: codec::Kind = codec::Kind::AssetsIndex;
+    const VERSION: u16 = 1;
+}
+
+impl Assets {
+    /// Splits the assets into an [`Index`] and the blob of icons it points into.
+    pub fn into_index(self) -> (Index, Vec<u8>) {
+        let mut blob = Vec::new();
+        let mut icons = BTreeMap::new();
+        let mut push = |icon, image: Image| {
+            let location = Location {
+                offset: blob.len() as u32,
+                length: image.as_ref().len() as u32,
+            };
+
+            blob.extend_from_slice(image.as_ref());
+            icons.insert(icon, location);
+        };
+
+        let champion = self
+            .champion
+            .0
+            .into_iter()
+            .map(|(id, assets)| {
+                push(Icon::Champion(id), assets.icon);
+                (id, assets.data)
+            })
+            .collect();
+        let rune = self
+            .rune
+            .0
+            .into_iter()
+            .map(|(id, assets)| {
+                push(Icon::Rune(id), assets.icon);
+                (id, assets.data)
+            })
+            .collect();
+        let spell = self
+            .spell
+            .0
+            .into_iter()
+            .map(|(id, assets)| {
+                push(Icon::Spell(id), assets.icon);
+                (id, assets.data)
+            })
+            .collect();
+        let item = self
+            .item
+            .0
+            .into_iter()
+            .map(|(id, assets)| {
+                push(Icon::Item(id), assets.icon);
+                (id, assets.data)
+            })
+            .collect();
+
+        let index = Index {
+            version: self.version,
+            champion,
+            rune,
+            spell,
+            item,
+            emblem: self.emblem,
+            icons,
+        };
+
+        (index, blob)
+    }
+}
@@ -1,19 +1,47 @@
 use crate::assets;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
-#[derive(bitcode::Encode, bitcode::Decode, PartialEq, Eq, Hash, Clone, Copy)]
+#[derive(
+    bitcode::Encode, bitcode::Decode, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy,
+)]
 pub struct Id(pub u32);
 
 impl Id {
-    pub fn from_key(key: &str) -> Self {
-        Self(key.parse().unwrap())
+    /// The id of a Data Dragon `key`, which is its number as a string.
+    pub fn from_key(key: &str) -> Option<Self> {
+        key.parse().ok().map(Self)
     }
 }
 
-#[derive(bitcode::Encode, bitcode::Decode)]
+#[derive(bitcode::Encode, bitcode::Decode, PartialEq)]
 pub struct Assets {
     pub icon: assets::Image,
+    pub data: Data,
+}
+
+/// Static data of an item.
+#[derive(bitcode::Encode, bitcode::Decode, Debug, Clone, PartialEq)]
+pub struct Data {
+    pub name: String,
+    /// The full description, with Data Dragon's markup (e.g. `<stats>`) stripped.
+    pub description: String,
+    /// A one line summary of the item.
+    pub plaintext: String,
+    pub gold: Gold,
+    /// The items this item is built from.
+    pub from: Vec<Id>,
+    /// The items this item builds into.
+    pub into: Vec<Id>,
+    pub tags: Vec<String>,
+}
+
+#[derive(bitcode::Encode, bitcode::Decode, Debug, Clone, Copy, Default, PartialEq)]
+pub struct Gold {
+    /// The cost of the item on top of its components.
+    pub base: u32,
+    pub total: u32,
+    pub sell: u32,
 }
 
 #[derive(bitcode::Encode, bitcode::Decode)]
-pub struct AssetMap(pub HashMap<Id, Assets>);
+pub struct AssetMap(pub BTreeMap<Id, Assets>);
@@ -1,13 +1,24 @@
 use crate::assets;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
-#[derive(bitcode::Encode, bitcode::Decode, PartialEq, Eq, Hash, Clone, Copy)]
+#[derive(
+    bitcode::Encode, bitcode::Decode, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy,
+)]
 pub struct Id(pub u16);
 
-#[derive(bitcode::Encode, bitcode::Decode)]
+#[derive(bitcode::Encode, bitcode::Decode, PartialEq)]
 pub struct Assets {
     pub icon: assets::Image,
+    pub data: Data,
+}
+
+/// Static data of a rune, or of a rune path.
+#[derive(bitcode::Encode, bitcode::Decode, Debug, Clone, PartialEq)]
+pub struct Data {
+    pub name: String,
+    /// A short description, with Data Dragon's markup stripped. Empty for rune paths.
+    pub description: String,
 }
 
 #[derive(bitcode::Encode, bitcode::Decode)]
-pub struct AssetMap(pub HashMap<Id, Assets>);
+pub struct AssetMap(pub BTreeMap<Id, Assets>);
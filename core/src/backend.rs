@@ -0,0 +1,315 @@
+#[cfg(not(feature = "dummy"))]
+use crate::ParseError;
+use crate::account::RiotId;
+use crate::assets::{Delta, Icon, Image, Index};
+use crate::{Assets, Region, RequestError, game, live, summoner};
+
+use bytes::Bytes;
+
+/// The position in the match history to request games from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cursor {
+    /// The most recent games.
+    Latest,
+    /// Games created before the given unix timestamp, in seconds.
+    Before(i64),
+    /// Games created after the given unix timestamp, in seconds.
+    After(i64),
+}
+
+/// A client for the backend serving Aery's data (e.g. `aery_worker`).
+#[derive(Debug, Clone)]
+pub struct Backend {
+    http: reqwest::Client,
+    #[cfg_attr(feature = "dummy", allow(dead_code))]
+    url: String,
+}
+
+impl Backend {
+    pub fn new(url: impl ToString) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            url: url.to_string(),
+        }
+    }
+
+    #[cfg(not(feature = "dummy"))]
+    pub async fn summoner(
+        &self,
+        region: Region,
+        riot_id: RiotId,
+    ) -> Result<summoner::Data, RequestError> {
+        let (Some(name), Some(tagline)) = (&riot_id.name, &riot_id.tagline) else {
+            return Err(ParseError::RiotId(riot_id.to_string()).into());
+        };
+
+        let path = format!("{}/summoner/{region}/{name}-{tagline}", self.url);
+        tracing::info!("Requesting `{riot_id}` ({region}) to {path}");
+
+        let bytes = self.get(path).await?;
+
+        Ok(summoner::Data::decode(&bytes)?)
+    }
+
+    #[cfg(feature = "dummy")]
+    pub async fn summoner(
+        &self,
+        _region: Region,
+        riot_id: RiotId,
+    ) -> Result<summoner::Data, RequestError> {
+        let name = riot_id.name.as_deref().unwrap_or("someone");
+        let tagline = riot_id.tagline.as_deref().unwrap_or("foo");
+
+        Ok(summoner::Data::dummy(name, tagline))
+    }
+
+    #[cfg(not(feature = "dummy"))]
+    pub async fn matches(
+        &self,
+        region: Region,
+        puuid: String,
+        cursor: Cursor,
+    ) -> Result<game::Map, RequestError> {
+        let mut path = format!("{}/matches/{puuid}?region={region}", self.url);
+        match cursor {
+            Cursor::Latest => {}
+            Cursor::Before(time) => path.push_str(&format!("&end_time={time}")),
+            Cursor::After(time) => path.push_str(&format!("&start_time={time}")),
+        }
+
+        tracing::info!("Requesting `{puuid}` ({region}) to {path}");
+
+        let bytes = self.get(path).await?;
+
+        Ok(game::Map::decode(&bytes)?)
+    }
+
+    #[cfg(feature = "dummy")]
+    pub async fn matches(
+        &self,
+        _region: Region,
+        _puuid: String,
+        _cursor: Cursor,
+    ) -> Result<game::Map, RequestError> {
+        Ok(game::Map::default())
+    }
+
+    #[cfg(not(feature = "dummy"))]
+    pub async fn timeline(
+        &self,
+        region: Region,
+        id: game::Id,
+    ) -> Result<game::Timeline, RequestError> {
+        let path = format!("{}/timeline/{}?region={region}", self.url, id.as_ref());
+        tracing::info!("Requesting timeline of `{}` to {path}", id.as_ref());
+
+        let bytes = self.get(path).await?;
+
+        Ok(game::Timeline::decode(&bytes)?)
+    }
+
+    #[cfg(feature = "dummy")]
+    pub async fn timeline(
+        &self,
+        _region: Region,
+        id: game::Id,
+    ) -> Result<game::Timeline, RequestError> {
+        Ok(game::Timeline::dummy(id))
+    }
+
+    /// Requests the game `puuid` is currently playing, if any.
+    #[cfg(not(feature = "dummy"))]
+    pub async fn live_game(
+        &self,
+        region: Region,
+        puuid: String,
+    ) -> Result<Option<live::Game>, RequestError> {
+        let path = format!("{}/live/{region}/{puuid}", self.url);
+        tracing::info!("Requesting live game of `{puuid}` ({region}) to {path}");
+
+        match self.get(path).await {
+            Ok(bytes) => Ok(Some(live::Game::decode(&bytes)?)),
+            Err(RequestError::NotFound) => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    #[cfg(feature = "dummy")]
+    pub async fn live_game(
+        &self,
+        _region: Region,
+        _puuid: String,
+    ) -> Result<Option<live::Game>, RequestError> {
+        Ok(Some(live::Game::dummy(crate::account::RiotId::new(
+            "someone", "foo",
+        ))))
+    }
+
+    /// Requests the leagues of the `puuids`, at most [`summoner::ranks::BATCH`] at once.
+    #[cfg(not(feature = "dummy"))]
+    pub async fn ranks(
+        &self,
+        region: Region,
+        puuids: Vec<String>,
+    ) -> Result<summoner::Ranks, RequestError> {
+        let path = format!("{}/ranks/{region}?puuids={}", self.url, puuids.join(","));
+        tracing::info!(
+            "Requesting the leagues of {} summoners ({region})",
+            puuids.len()
+        );
+
+        let bytes = self.get(path).await?;
+
+        Ok(summoner::Ranks::decode(&bytes)?)
+    }
+
+    #[cfg(feature = "dummy")]
+    pub async fn ranks(
+        &self,
+        _region: Region,
+        puuids: Vec<String>,
+    ) -> Result<summoner::Ranks, RequestError> {
+        Ok(summoner::Ranks::dummy(&puuids))
+    }
+
+    #[cfg(not(feature = "dummy"))]
+    pub async fn assets(&self, version: String) -> Result<Assets, RequestError> {
+        let path = format!("{}/assets/{version}", self.url);
+        tracing::info!("Requesting assets to {path}");
+
+        let bytes = self.get(path).await?;
+
+        Ok(Assets::decode(bytes.to_vec())?)
+    }
+
+    #[cfg(feature = "dummy")]
+    pub async fn assets(&self, _version: String) -> Result<Assets, RequestError> {
+        let bytes = std::fs::read("assets/dummy/assets.aery").map_err(RequestError::internal)?;
+
+        Ok(Assets::decode(bytes)?)
+    }
+
+    /// Requests the index of the assets `version`, whose icons can be requested with
+    /// [`Backend::icons`].
+    #[cfg(not(feature = "dummy"))]
+    pub async fn assets_index(&self, version: String) -> Result<Index, RequestError> {
+        let path = format!("{}/assets/{version}/index", self.url);
+        tracing::info!("Requesting assets index to {path}");
+
+        let bytes = self.get(path).await?;
+
+        Ok(Index::decode(&bytes)?)
+    }
+
+    #[cfg(feature = "dummy")]
+    pub async fn assets_index(&self, version: String) -> Result<Index, RequestError> {
+        let (index, _) = self.assets(version).await?.into_index();
+
+        Ok(index)
+    }
+
+    /// Requests the given icons of the assets `version` concurrently.
+    #[cfg(not(feature = "dummy"))]
+    pub async fn icons(
+        &self,
+        version: String,
+        icons: Vec<Icon>,
+    ) -> Vec<(Icon, Result<Image, RequestError>)> {
+        let requests = icons.into_iter().map(|icon| {
+            let path = format!(
+                "{}/assets/{version}/icons/{}/{}",
+                self.url,
+                icon.kind(),
+                icon.id()
+            );
+
+            async move { (icon, self.get(path).await.map(Image::from)) }
+        });
+
+        futures::future::join_all(requests).await
+    }
+
+    #[cfg(feature = "dummy")]
+    pub async fn icons(
+        &self,
+        version: String,
+        icons: Vec<Icon>,
+    ) -> Vec<(Icon, Result<Image, RequestError>)> {
+        let (index, blob) = match self.assets(version).await {
+            Ok(assets) => assets.into_index(),
+            Err(error) => {
+                return icons
+                    .into_iter()
+                    .map(|icon| (icon, Err(error.clone())))
+                    .collect();
+            }
+        };
+
+        icons
+            .into_iter()
+            .map(|icon| {
+                let image = index
+                    .icons
+                    .get(&icon)
+                    .and_then(|location| location.get(&blob))
+                    .map(|bytes| Image::from(bytes.to_vec()))
+                    .ok_or(RequestError::NotFound);
+
+                (icon, image)
+            })
+            .collect()
+    }
+
+    /// Requests the changes from the assets `version` to the latest ones.
+    #[cfg(not(feature = "dummy"))]
+    pub async fn assets_delta(&self, version: String) -> Result<Delta, RequestError> {
+        let path = format!("{}/assets/delta/{version}", self.url);
+        tracing::info!("Requesting assets delta to {path}");
+
+        let bytes = self.get(path).await?;
+
+        Ok(Delta::decode(&bytes)?)
+    }
+
+    #[cfg(feature = "dummy")]
+    pub async fn assets_delta(&self, _version: String) -> Result<Delta, RequestError> {
+        Err(RequestError::NotFound)
+    }
+
+    /// Returns the Data Dragon version of the latest assets.
+    #[cfg(not(feature = "dummy"))]
+    pub async fn assets_version(&self) -> Result<String, RequestError> {
+        let path = format!("{}/assets/version", self.url);
+        let bytes = self.get(path).await?;
+
+        String::from_utf8(bytes.to_vec()).map_err(RequestError::internal)
+    }
+
+    #[cfg(feature = "dummy")]
+    pub async fn assets_version(&self) -> Result<String, RequestError> {
+        self.assets(String::from("latest"))
+            .await
+            .map(|assets| assets.version)
+    }
+
+    #[cfg_attr(feature = "dummy", allow(dead_code))]
+    async fn get(&self, path: String) -> Result<Bytes, RequestError> {
+        let response = self
+            .http
+            .get(path)
+            .send()
+            .await
+            .map_err(RequestError::internal)?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(RequestError::NotFound);
+        }
+
+        response
+            .error_for_status()
+            .map_err(RequestError::internal)?
+            .bytes()
+            .await
+            .map_err(RequestError::internal)
+    }
+}
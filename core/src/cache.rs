@@ -0,0 +1,219 @@
+//! A persistent cache of fetched profiles, with their games, timelines and rank history,
+//! the leagues of the players met in games and the last downloaded assets.
+//!
+//! Everything is stored under a root directory:
+//!
+//! ```text
+//! root/
+//! ├── assets.aery               index of the last downloaded assets
+//! ├── icons-15.13.1             icons of the assets, pointed into by the index
+//! ├── last                      region and puuid of the last saved profile
+//! └── BR1/
+//!     ├── accounts/name-tag     puuid of the riot id
+//!     ├── ranks.aery            leagues of the players met in games
+//!     ├── timelines/BR1_123.aery
+//!     └── puuid/
+//!         ├── profile.aery      summoner, leagues and latest games
+//!         ├── games.aery        every game known of the summoner
+//!         └── leagues.aery      snapshots of the leagues of the summoner
+//! ```
+use crate::account::RiotId;
+use crate::assets::{Image, Index, index};
+use crate::codec::{self, Payload};
+use crate::{Assets, Region, game, summoner};
+
+use std::fs;
+use std::io::{self, Read, Seek};
+use std::path::{Path, PathBuf};
+
+const ASSETS: &str = "assets.aery";
+const ICONS: &str = "icons-";
+const LAST: &str = "last";
+const ACCOUNTS: &str = "accounts";
+const PROFILE: &str = "profile.aery";
+const GAMES: &str = "games.aery";
+const LEAGUES: &str = "leagues.aery";
+const RANKS: &str = "ranks.aery";
+const TIMELINES: &str = "timelines";
+
+#[derive(Debug, Clone)]
+pub struct Store {
+    root: PathBuf,
+}
+
+impl Store {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Returns the index of the cached assets, whose icons can be read with [`Store::icon`].
+    pub fn assets_index(&self) -> Option<Index> {
+        read(&self.root.join(ASSETS))
+    }
+
+    /// Returns the cached assets with all of their icons.
+    pub fn assets(&self) -> Option<Assets> {
+        let index = self.assets_index()?;
+        let icons = fs::read(self.icons_path(&index.version)).ok()?;
+
+        index.into_assets(&icons)
+    }
+
+    pub fn icon(&self, version: &str, location: index::Location) -> io::Result<Image> {
+        let mut file = fs::File::open(self.icons_path(version))?;
+        let mut bytes = vec![0; location.length as usize];
+
+        file.seek(io::SeekFrom::Start(location.offset as u64))?;
+        file.read_exact(&mut bytes)?;
+
+        Ok(Image::from(bytes))
+    }
+
+    /// Saves assets split with [`Assets::into_index`].
+    pub fn save_assets(&self, index: &Index, icons: &[u8]) -> io::Result<()> {
+        fs::create_dir_all(&self.root)?;
+        // The icons are written first, so the saved index never points into missing icons
+        write(&self.icons_path(&index.version), icons)?;
+        write(&self.root.join(ASSETS), &index.encode())?;
+
+        let current = format!("{ICONS}{}", index.version);
+        for entry in fs::read_dir(&self.root)?.filter_map(Result::ok) {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+
+            if name.starts_with(ICONS) && name != current {
+                let _ = fs::remove_file(entry.path());
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn profile(&self, region: Region, riot_id: &RiotId) -> Option<summoner::Data> {
+        let puuid = fs::read_to_string(self.account_path(region, riot_id)?).ok()?;
+
+        read(&self.summoner_path(region, &puuid).join(PROFILE))
+    }
+
+    /// Returns the last saved profile.
+    pub fn last(&self) -> Option<(Region, summoner::Data)> {
+        let last = fs::read_to_string(self.root.join(LAST)).ok()?;
+        let (region, puuid) = last.split_once('\n')?;
+        let region = region.parse().ok()?;
+
+        read(&self.summoner_path(region, puuid).join(PROFILE)).map(|profile| (region, profile))
+    }
+
+    pub fn games(&self, region: Region, puuid: &str) -> game::Map {
+        read(&self.summoner_path(region, puuid).join(GAMES)).unwrap_or_default()
+    }
+
+    pub fn leagues(&self, region: Region, puuid: &str) -> summoner::History {
+        read(&self.summoner_path(region, puuid).join(LEAGUES)).unwrap_or_default()
+    }
+
+    pub fn save_profile(&self, region: Region, profile: &summoner::Data) -> io::Result<()> {
+        let puuid = profile.summoner.puuid();
+        let path = self.summoner_path(region, puuid);
+
+        fs::create_dir_all(&path)?;
+        write(&path.join(PROFILE), &profile.encode())?;
+
+        if let Some(account) = self.account_path(region, &profile.summoner.account.riot_id) {
+            fs::create_dir_all(self.root.join(region.to_string()).join(ACCOUNTS))?;
+            write(&account, puuid.as_bytes())?;
+        }
+
+        write(
+            &self.root.join(LAST),
+            format!("{region}\n{puuid}").as_bytes(),
+        )
+    }
+
+    pub fn save_games(&self, region: Region, puuid: &str, games: &game::Map) -> io::Result<()> {
+        let path = self.summoner_path(region, puuid);
+
+        fs::create_dir_all(&path)?;
+        write(&path.join(GAMES), &games.encode())
+    }
+
+    pub fn save_leagues(
+        &self,
+        region: Region,
+        puuid: &str,
+        history: &summoner::History,
+    ) -> io::Result<()> {
+        let path = self.summoner_path(region, puuid);
+
+        fs::create_dir_all(&path)?;
+        write(&path.join(LEAGUES), &history.encode())
+    }
+
+    pub fn ranks(&self, region: Region) -> summoner::Ranks {
+        read(&self.root.join(region.to_string()).join(RANKS)).unwrap_or_default()
+    }
+
+    pub fn save_ranks(&self, region: Region, ranks: &summoner::Ranks) -> io::Result<()> {
+        let path = self.root.join(region.to_string());
+
+        fs::create_dir_all(&path)?;
+        write(&path.join(RANKS), &ranks.encode())
+    }
+
+    pub fn timeline(&self, region: Region, id: &game::Id) -> Option<game::Timeline> {
+        read(&self.timeline_path(region, id))
+    }
+
+    /// Saves the timeline of a finished game, which never changes afterwards.
+    pub fn save_timeline(&self, region: Region, timeline: &game::Timeline) -> io::Result<()> {
+        fs::create_dir_all(self.root.join(region.to_string()).join(TIMELINES))?;
+        write(
+            &self.timeline_path(region, &timeline.id),
+            &timeline.encode(),
+        )
+    }
+
+    fn timeline_path(&self, region: Region, id: &game::Id) -> PathBuf {
+        self.root
+            .join(region.to_string())
+            .join(TIMELINES)
+            .join(format!("{}.aery", id.as_ref()))
+    }
+
+    fn icons_path(&self, version: &str) -> PathBuf {
+        self.root.join(format!("{ICONS}{version}"))
+    }
+
+    fn summoner_path(&self, region: Region, puuid: &str) -> PathBuf {
+        self.root.join(region.to_string()).join(puuid)
+    }
+
+    fn account_path(&self, region: Region, riot_id: &RiotId) -> Option<PathBuf> {
+        let name = riot_id.name.as_deref()?;
+        let tagline = riot_id.tagline.as_deref()?;
+
+        Some(
+            self.root
+                .join(region.to_string())
+                .join(ACCOUNTS)
+                .join(format!("{name}-{tagline}").to_lowercase()),
+        )
+    }
+}
+
+fn read<T: Payload>(path: &Path) -> Option<T> {
+    let bytes = fs::read(path).ok()?;
+
+    codec::decode(&bytes)
+        .inspect_err(|error| tracing::warn!("Ignoring cached {}: {error}", path.display()))
+        .ok()
+}
+
+/// Writes to a temporary file first, so a crash never leaves a partially written file behind.
+fn write(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    let mut temporary = path.as_os_str().to_owned();
+    temporary.push(".tmp");
+
+    fs::write(&temporary, bytes)?;
+    fs::rename(temporary, path)
+}
@@ -7,6 +7,16 @@ impl Client {
     pub fn new(key: String) -> Self {
         Client(Arc::new(riven::RiotApi::new(key)))
     }
+
+    /// Creates a [`Client`] pointing to a different Riot API host.
+    ///
+    /// The `base_url` must contain a `{}` placeholder, which is replaced by the route
+    /// (e.g. `http://localhost:8080/{}`).
+    pub fn with_base_url(key: String, base_url: String) -> Self {
+        let config = riven::RiotApiConfig::with_key(key).set_base_url(base_url);
+
+        Client(Arc::new(riven::RiotApi::new(config)))
+    }
 }
 
 impl AsRef<riven::RiotApi> for Client {
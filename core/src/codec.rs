@@ -0,0 +1,283 @@
+//! The wire format shared by every encoded payload.
+//!
+//! A payload is framed by a small header:
+//!
+//! | Field    | Size | Description                                      |
+//! | -------- | ---- | ------------------------------------------------ |
+//! | magic    | 4    | Always `AERY`                                    |
+//! | kind     | 1    | The [`Kind`] of payload                          |
+//! | version  | 2    | The schema version of the payload, little endian |
+//! | checksum | 4    | CRC-32 of the body, little endian                |
+//!
+//! Followed by the body, a `bitcode` encoded value compressed with `lz4`.
+
+const MAGIC: [u8; 4] = *b"AERY";
+const HEADER_SIZE: usize = MAGIC.len() + 1 + 2 + 4;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum DecodeError {
+    #[error("payload is truncated")]
+    Truncated,
+    #[error("payload is not an aery payload")]
+    InvalidMagic,
+    #[error("expected a {expected:?} payload, found {found}")]
+    KindMismatch { expected: Kind, found: u8 },
+    #[error("unsupported {kind:?} schema version {version}")]
+    UnsupportedVersion { kind: Kind, version: u16 },
+    #[error("payload checksum does not match")]
+    ChecksumMismatch,
+    #[error("decompression failed: {0}")]
+    Decompression(String),
+    #[error("payload does not match the expected schema")]
+    SchemaMismatch,
+}
+
+impl From<lz4_flex::block::DecompressError> for DecodeError {
+    fn from(error: lz4_flex::block::DecompressError) -> Self {
+        match error {
+            lz4_flex::block::DecompressError::ExpectedAnotherByte => Self::Truncated,
+            error => Self::Decompression(error.to_string()),
+        }
+    }
+}
+
+impl From<bitcode::Error> for DecodeError {
+    fn from(_error: bitcode::Error) -> Self {
+        Self::SchemaMismatch
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Assets = 0,
+    Summoner = 1,
+    Games = 2,
+    AssetsDelta = 3,
+    AssetsIndex = 4,
+    Timeline = 5,
+    LeagueHistory = 6,
+    Ranks = 7,
+    LiveGame = 8,
+}
+
+/// A value that can be sent over the wire.
+pub trait Payload: bitcode::Encode + bitcode::DecodeOwned {
+    const KIND: Kind;
+
+    /// The current schema version, bump it whenever the encoded layout changes.
+    const VERSION: u16;
+
+    /// Decodes a body encoded with an older schema `version`.
+    fn migrate(version: u16, _body: &[u8]) -> Result<Self, DecodeError> {
+        Err(DecodeError::UnsupportedVersion {
+            kind: Self::KIND,
+            version,
+        })
+    }
+}
+
+pub(crate) fn encode<T: Payload>(value: &T) -> Vec<u8> {
+    let body = lz4_flex::compress_prepend_size(&bitcode::encode(value));
+
+    let mut bytes = Vec::with_capacity(HEADER_SIZE + body.len());
+    bytes.extend_from_slice(&MAGIC);
+    bytes.push(T::KIND as u8);
+    bytes.extend_from_slice(&T::VERSION.to_le_bytes());
+    bytes.extend_from_slice(&crc32(&body).to_le_bytes());
+    bytes.extend_from_slice(&body);
+
+    bytes
+}
+
+pub(crate) fn decode<T: Payload>(bytes: &[u8]) -> Result<T, DecodeError> {
+    if bytes.len() < MAGIC.len() {
+        return Err(DecodeError::Truncated);
+    }
+
+    if bytes[..MAGIC.len()] != MAGIC {
+        return Err(DecodeError::InvalidMagic);
+    }
+
+    if bytes.len() < HEADER_SIZE {
+        return Err(DecodeError::Truncated);
+    }
+
+    let (header, body) = bytes.split_at(HEADER_SIZE);
+    let kind = header[4];
+    let version = u16::from_le_bytes([header[5], header[6]]);
+    let checksum = u32::from_le_bytes([header[7], header[8], header[9], header[10]]);
+
+    if kind != T::KIND as u8 {
+        return Err(DecodeError::KindMismatch {
+            expected: T::KIND,
+            found: kind,
+        });
+    }
+
+    if checksum != crc32(body) {
+        return Err(DecodeError::ChecksumMismatch);
+    }
+
+    match version.cmp(&T::VERSION) {
+        std::cmp::Ordering::Equal => decode_body(body),
+        std::cmp::Ordering::Less => T::migrate(version, body),
+        std::cmp::Ordering::Greater => Err(DecodeError::UnsupportedVersion {
+            kind: T::KIND,
+            version,
+        }),
+    }
+}
+
+/// Decodes the `lz4` compressed, `bitcode` encoded body of a payload.
+pub fn decode_body<T: bitcode::DecodeOwned>(body: &[u8]) -> Result<T, DecodeError> {
+    // `lz4_flex` prepends the uncompressed size as a `u32`
+    if body.len() < size_of::<u32>() {
+        return Err(DecodeError::Truncated);
+    }
+
+    let decompressed = lz4_flex::decompress_size_prepended(body)?;
+
+    Ok(bitcode::decode(&decompressed)?)
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    const TABLE: [u32; 256] = {
+        let mut table = [0; 256];
+        let mut i = 0;
+
+        while i < 256 {
+            let mut crc = i as u32;
+            let mut bit = 0;
+
+            while bit < 8 {
+                crc = if crc & 1 == 1 {
+                    (crc >> 1) ^ 0xEDB8_8320
+                } else {
+                    crc >> 1
+                };
+                bit += 1;
+            }
+
+            table[i] = crc;
+            i += 1;
+        }
+
+        table
+    };
+
+    !bytes.iter().fold(!0, |crc, &byte| {
+        TABLE[((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, bitcode::Encode, bitcode::Decode)]
+    struct Sample {
+        name: String,
+        values: Vec<u32>,
+    }
+
+    impl Payload for Sample {
+        const KIND: Kind = Kind::Summoner;
+        const VERSION: u16 = 2;
+    }
+
+    #[derive(Debug, PartialEq, bitcode::Encode, bitcode::Decode)]
+    struct Other(u32);
+
+    impl Payload for Other {
+        const KIND: Kind = Kind::Games;
+        const VERSION: u16 = 1;
+    }
+
+    fn sample() -> Sample {
+        Sample {
+            name: String::from("aery"),
+            values: (0..64).collect(),
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let sample = sample();
+
+        assert_eq!(decode::<Sample>(&encode(&sample)), Ok(sample));
+    }
+
+    #[test]
+    fn invalid_magic() {
+        let mut bytes = encode(&sample());
+        bytes[0] = b'X';
+
+        assert_eq!(decode::<Sample>(&bytes), Err(DecodeError::InvalidMagic));
+    }
+
+    #[test]
+    fn kind_mismatch() {
+        let bytes = encode(&Other(42));
+
+        assert_eq!(
+            decode::<Sample>(&bytes),
+            Err(DecodeError::KindMismatch {
+                expected: Kind::Summoner,
+                found: Kind::Games as u8,
+            })
+        );
+    }
+
+    #[test]
+    fn checksum_mismatch() {
+        let mut bytes = encode(&sample());
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0xFF;
+
+        assert_eq!(decode::<Sample>(&bytes), Err(DecodeError::ChecksumMismatch));
+    }
+
+    #[test]
+    fn truncated() {
+        let bytes = encode(&sample());
+
+        assert_eq!(decode::<Sample>(&bytes[..2]), Err(DecodeError::Truncated));
+        assert_eq!(
+            decode::<Sample>(&bytes[..HEADER_SIZE - 1]),
+            Err(DecodeError::Truncated)
+        );
+    }
+
+    #[test]
+    fn newer_version() {
+        let mut bytes = encode(&sample());
+        bytes[5..7].copy_from_slice(&(Sample::VERSION + 1).to_le_bytes());
+
+        assert_eq!(
+            decode::<Sample>(&bytes),
+            Err(DecodeError::UnsupportedVersion {
+                kind: Kind::Summoner,
+                version: Sample::VERSION + 1,
+            })
+        );
+    }
+
+    #[test]
+    fn older_version_without_migration() {
+        let mut bytes = encode(&sample());
+        bytes[5..7].copy_from_slice(&(Sample::VERSION - 1).to_le_bytes());
+
+        assert_eq!(
+            decode::<Sample>(&bytes),
+            Err(DecodeError::UnsupportedVersion {
+                kind: Kind::Summoner,
+                version: Sample::VERSION - 1,
+            })
+        );
+    }
+
+    #[test]
+    fn crc32_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+}
@@ -5,6 +5,18 @@ pub mod item;
 pub mod rune;
 pub use item::Item;
 
+pub mod team;
+pub mod timeline;
+pub use team::Team;
+pub use timeline::Timeline;
+
+pub(crate) mod v1;
+pub(crate) mod v2;
+pub(crate) mod v3;
+
+use crate::backend::Cursor;
+use crate::codec::{self, DecodeError, Payload};
+use crate::{Client, Region};
 use riven::models::match_v5;
 use std::collections::BTreeMap;
 use std::fmt;
@@ -36,13 +48,6 @@ pub enum RequestError {
     RequestFailed(#[from] riven::RiotApiError),
 }
 
-#[allow(dead_code)]
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-pub struct Event(pub riven::models::match_v5::EventsTimeLine);
-
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-pub struct Timeline(pub Vec<Event>);
-
 #[derive(Debug, Clone, Copy, PartialEq, Eq, bitcode::Encode, bitcode::Decode)]
 pub enum Result {
     Defeat,
@@ -107,18 +112,86 @@ impl Ord for TimeId {
 pub struct Map(BTreeMap<TimeId, Game>);
 
 impl Map {
+    /// The amount of games requested per page.
+    pub const PAGE_SIZE: i32 = 10;
+
+    pub async fn fetch(
+        client: &Client,
+        region: Region,
+        puuid: &str,
+        start_time: Option<i64>,
+        end_time: Option<i64>,
+    ) -> core::result::Result<Self, RequestError> {
+        let ids = client
+            .as_ref()
+            .match_v5()
+            .get_match_ids_by_puuid(
+                region.into(),
+                puuid,
+                Some(Self::PAGE_SIZE),
+                end_time,
+                None,
+                start_time,
+                None,
+                None,
+            )
+            .await?;
+
+        let games = futures::future::try_join_all(
+            ids.into_iter()
+                .map(|id| Game::fetch(client, region, Id(id))),
+        )
+        .await?;
+
+        Ok(games
+            .into_iter()
+            .map(|game| (game.id.clone(), game))
+            .collect())
+    }
+
     pub fn len(&self) -> usize {
         self.0.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn newest(&self) -> Option<&Game> {
+        self.0.values().next()
+    }
+
+    pub fn oldest(&self) -> Option<&Game> {
+        self.0.values().next_back()
+    }
+
+    /// Merges a page of `games` into the map.
+    ///
+    /// A full page that does not reach the known games older than it leaves a gap in the
+    /// history, in which case the cursor of the page right below it is returned so the gap
+    /// can be filled.
+    pub fn merge(&mut self, games: Map) -> Option<Cursor> {
+        let gap = match (games.newest(), games.oldest()) {
+            (Some(newest), Some(oldest)) if games.len() >= Self::PAGE_SIZE as usize => self
+                .0
+                .values()
+                .find(|game| game.created_at < newest.created_at)
+                .filter(|known| known.created_at < oldest.created_at)
+                .map(|_| Cursor::Before(oldest.created_at / 1000)),
+            _ => None,
+        };
+
+        self.0.extend(games.0);
+
+        gap
+    }
+
     pub fn encode(&self) -> Vec<u8> {
-        let bytes = bitcode::encode(self);
-        lz4_flex::compress_prepend_size(&bytes)
+        codec::encode(self)
     }
 
-    pub fn decode(bytes: &[u8]) -> Self {
-        let decompressed = lz4_flex::decompress_size_prepended(bytes).unwrap();
-        bitcode::decode(&decompressed).unwrap()
+    pub fn decode(bytes: &[u8]) -> core::result::Result<Self, DecodeError> {
+        codec::decode(bytes)
     }
 
     pub fn iter(&self) -> impl Iterator<Item = (&Id, &Game)> {
@@ -126,6 +199,33 @@ impl Map {
     }
 }
 
+impl Payload for Map {
+    const KIND: codec::Kind = codec::Kind::Games;
+    const VERSION: u16 = 5;
+
+    fn migrate(version: u16, body: &[u8]) -> core::result::Result<Self, DecodeError> {
+        let mut map = match version {
+            1 => codec::decode_body::<v1::Map>(body).map(Map::from)?,
+            2 => codec::decode_body::<v2::Map>(body).map(Map::from)?,
+            3 => codec::decode_body::<v3::Map>(body).map(Map::from)?,
+            // Version 4 has the current layout, but cached queues named since by id
+            4 => codec::decode_body::<Map>(body)?,
+            _ => {
+                return Err(DecodeError::UnsupportedVersion {
+                    kind: Self::KIND,
+                    version,
+                });
+            }
+        };
+
+        for game in map.0.values_mut() {
+            game.queue = game.queue.remap();
+        }
+
+        Ok(map)
+    }
+}
+
 impl FromIterator<(Id, Game)> for Map {
     fn from_iter<T: IntoIterator<Item = (Id, Game)>>(iter: T) -> Self {
         Self(BTreeMap::from_iter(
@@ -148,9 +248,24 @@ pub struct Game {
     pub created_at: i64,
     pub duration: i64,
     pub players: Vec<Player>,
+    pub teams: Vec<Team>,
 }
 
 impl Game {
+    pub async fn fetch(
+        client: &Client,
+        region: Region,
+        id: Id,
+    ) -> core::result::Result<Self, RequestError> {
+        client
+            .as_ref()
+            .match_v5()
+            .get_match(region.into(), &id.0)
+            .await?
+            .map(Game::from)
+            .ok_or(RequestError::NotFound)
+    }
+
     pub fn created_at_time(&self) -> time::OffsetDateTime {
         time::OffsetDateTime::from_unix_timestamp_nanos(self.created_at as i128 * 1_000_000)
             .unwrap()
@@ -166,6 +281,14 @@ impl Game {
             .find(|participant| participant.puuid == puuid)
     }
 
+    pub fn team(&self, id: crate::Team) -> Option<&Team> {
+        self.teams.iter().find(|team| team.id == id)
+    }
+
+    pub fn is_arena(&self) -> bool {
+        self.queue == Queue::Arena
+    }
+
     #[cfg(feature = "dummy")]
     pub fn dummy(riot_id: crate::account::RiotId) -> Self {
         use crate::{Champion, Team, account};
@@ -244,11 +367,74 @@ impl Game {
         ];
 
         Game {
-            id: Id(String::from("foo")),
+            // The puuid of the summoner lets the dummy timeline name the same players
+            id: Id(format!("dummy-{}", players[0].puuid)),
             queue: Queue::RankedSolo,
             created_at: 1751830754821,
             duration: 2205,
             players,
+            teams: vec![
+                self::Team::dummy(Team::BLUE, Result::Defeat),
+                self::Team::dummy(Team::RED, Result::Victory),
+            ],
+        }
+    }
+
+    #[cfg(feature = "dummy")]
+    pub fn dummy_arena(riot_id: crate::account::RiotId) -> Self {
+        use crate::{Champion, Team, account};
+
+        const CHAMPIONS: [u32; 16] = [
+            799, 233, 800, 901, 902, 14, 9, 1, 22, 12, 157, 238, 91, 84, 555, 266,
+        ];
+
+        let players: Vec<Player> = CHAMPIONS
+            .into_iter()
+            .enumerate()
+            .map(|(index, champion)| {
+                let subteam = index as u8 / 2 + 1;
+                // Shifted so the subteam of the summoner comes in 3rd
+                let placement = (subteam + 1) % 8 + 1;
+                let riot_id = if index == 0 {
+                    riot_id.clone()
+                } else {
+                    account::RiotId::new(format!("Contender {index}"), "cherry")
+                };
+
+                let mut player = Player::dummy(
+                    riot_id,
+                    if subteam.is_multiple_of(2) {
+                        Team::RED
+                    } else {
+                        Team::BLUE
+                    },
+                    Role::Mid,
+                    Champion(champion),
+                    Result::from(placement <= 4),
+                );
+
+                player.role = None;
+                player.arena = Some(player::Arena {
+                    subteam,
+                    placement,
+                    augments: vec![
+                        player::Augment(1205),
+                        player::Augment(1041),
+                        player::Augment(1160),
+                    ],
+                });
+
+                player
+            })
+            .collect();
+
+        Game {
+            id: Id(String::from("cherry")),
+            queue: Queue::Arena,
+            created_at: 1751827154821,
+            duration: 1214,
+            teams: self::Team::from_players(&players),
+            players,
         }
     }
 }
@@ -262,7 +448,25 @@ impl From<match_v5::Match> for Game {
         } else {
             time::Duration::milliseconds(game.info.game_duration).whole_seconds()
         };
-        let players = game.info.participants.iter().map(Player::from).collect();
+        let players: Vec<Player> = game.info.participants.iter().map(Player::from).collect();
+        let teams = if game.info.teams.is_empty() {
+            Team::from_players(&players)
+        } else {
+            game.info
+                .teams
+                .iter()
+                .map(|team| {
+                    let mut team = Team::from(team);
+
+                    // Remakes and surrenders are only known by the players
+                    if let Some(player) = players.iter().find(|player| player.team == team.id) {
+                        team.result = player.result;
+                    }
+
+                    team
+                })
+                .collect()
+        };
 
         Self {
             id,
@@ -270,6 +474,7 @@ impl From<match_v5::Match> for Game {
             created_at: game.info.game_creation,
             duration,
             players,
+            teams,
         }
     }
 }
@@ -321,6 +526,84 @@ pub enum Queue {
 
     Other(u16),
     Unknown(u16),
+
+    // New variants go last, so cached games keep decoding
+    /// CHERRY
+    Arena,
+    /// SWIFTPLAY
+    Swiftplay,
+    /// QUICKPLAY
+    Quickplay,
+    /// URF
+    URF,
+    /// ARURF
+    ARURF,
+    /// ONEFORALL
+    OneForAll,
+    /// NEXUSBLITZ
+    NexusBlitz,
+    /// ULTBOOK
+    UltimateSpellbook,
+    /// KIWI
+    ARAMMayhem,
+}
+
+impl Queue {
+    /// The queue of an id cached before the queue was named.
+    pub(crate) fn remap(self) -> Self {
+        match self {
+            Queue::Other(id) | Queue::Unknown(id) => Queue::from(riven::consts::Queue::from(id)),
+            queue => queue,
+        }
+    }
+
+    pub fn map(&self) -> MapKind {
+        match self {
+            Queue::ARAM | Queue::ARAMMayhem => MapKind::HowlingAbyss,
+            Queue::Arena => MapKind::RingsOfWrath,
+            Queue::NexusBlitz => MapKind::NexusBlitz,
+            Queue::Other(_) | Queue::Unknown(_) => MapKind::Other,
+            Queue::Custom
+            | Queue::Blind
+            | Queue::Draft
+            | Queue::RankedSolo
+            | Queue::RankedFlex
+            | Queue::Clash
+            | Queue::BotIntro
+            | Queue::BotBeginner
+            | Queue::BotIntermediate
+            | Queue::Swiftplay
+            | Queue::Quickplay
+            | Queue::URF
+            | Queue::ARURF
+            | Queue::OneForAll
+            | Queue::UltimateSpellbook => MapKind::SummonersRift,
+        }
+    }
+}
+
+/// The map a [`Queue`] is played on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MapKind {
+    SummonersRift,
+    HowlingAbyss,
+    RingsOfWrath,
+    NexusBlitz,
+    Other,
+}
+
+impl fmt::Display for MapKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            MapKind::SummonersRift => "Summoner's Rift",
+            MapKind::HowlingAbyss => "Howling Abyss",
+            MapKind::RingsOfWrath => "Rings of Wrath",
+            MapKind::NexusBlitz => "Nexus Blitz",
+            MapKind::Other => "Unknown map",
+        };
+
+        write!(f, "{name}")
+    }
 }
 
 impl fmt::Display for Queue {
@@ -338,6 +621,15 @@ impl fmt::Display for Queue {
             Queue::BotIntermediate => "Bot (Intermediate)",
             Queue::Other(_) => "Event",
             Queue::Unknown(_) => "Unknown",
+            Queue::Arena => "Arena",
+            Queue::Swiftplay => "Swiftplay",
+            Queue::Quickplay => "Quickplay",
+            Queue::URF => "URF",
+            Queue::ARURF => "ARURF",
+            Queue::OneForAll => "One for All",
+            Queue::NexusBlitz => "Nexus Blitz",
+            Queue::UltimateSpellbook => "Ultimate Spellbook",
+            Queue::ARAMMayhem => "ARAM Mayhem",
         };
 
         write!(f, "{name}")
@@ -361,6 +653,15 @@ impl From<Queue> for riven::consts::Queue {
             }
             Queue::Other(id) => riven::consts::Queue::from(id),
             Queue::Unknown(id) => riven::consts::Queue::from(id),
+            Queue::Arena => riven::consts::Queue::from(1700),
+            Queue::Swiftplay => riven::consts::Queue::from(480),
+            Queue::Quickplay => riven::consts::Queue::from(490),
+            Queue::URF => riven::consts::Queue::from(1900),
+            Queue::ARURF => riven::consts::Queue::from(900),
+            Queue::OneForAll => riven::consts::Queue::from(1020),
+            Queue::NexusBlitz => riven::consts::Queue::from(1300),
+            Queue::UltimateSpellbook => riven::consts::Queue::from(1400),
+            Queue::ARAMMayhem => riven::consts::Queue::from(2400),
         }
     }
 }
@@ -369,11 +670,7 @@ impl From<riven::consts::Queue> for Queue {
     fn from(queue: riven::consts::Queue) -> Queue {
         use riven::consts::Queue as ApiQueue;
 
-        if !queue.is_known() {
-            tracing::debug!("Unknown queue id encountered ({}).", queue.0);
-            return Queue::Unknown(queue.0);
-        }
-
+        // Recent queues are matched by id, as they may be missing from `riven`
         match queue {
             ApiQueue::CUSTOM => Queue::Custom,
             ApiQueue::SUMMONERS_RIFT_5V5_BLIND_PICK => Queue::Blind,
@@ -385,7 +682,102 @@ impl From<riven::consts::Queue> for Queue {
             ApiQueue::SUMMONERS_RIFT_CO_OP_VS_AI_INTRO_BOT => Queue::BotIntro,
             ApiQueue::SUMMONERS_RIFT_CO_OP_VS_AI_BEGINNER_BOT => Queue::BotBeginner,
             ApiQueue::SUMMONERS_RIFT_CO_OP_VS_AI_INTERMEDIATE_BOT => Queue::BotIntermediate,
+            ApiQueue(1700 | 1710) => Queue::Arena,
+            ApiQueue(480) => Queue::Swiftplay,
+            ApiQueue(490) => Queue::Quickplay,
+            ApiQueue(76 | 1900) => Queue::URF,
+            ApiQueue(900 | 1010) => Queue::ARURF,
+            ApiQueue(1020) => Queue::OneForAll,
+            ApiQueue(1200 | 1300) => Queue::NexusBlitz,
+            ApiQueue(1400) => Queue::UltimateSpellbook,
+            ApiQueue(2400) => Queue::ARAMMayhem,
+            ApiQueue(id) if !queue.is_known() => {
+                tracing::debug!("Unknown queue id encountered ({id}).");
+                Queue::Unknown(id)
+            }
             ApiQueue(id) => Queue::Other(id),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A page of games created at the given seconds.
+    fn page(seconds: impl IntoIterator<Item = i64>) -> Map {
+        seconds
+            .into_iter()
+            .map(|second| {
+                let id = Id(format!("BR1_{second}"));
+                let game = Game {
+                    id: id.clone(),
+                    queue: Queue::RankedSolo,
+                    created_at: second * 1000,
+                    duration: 0,
+                    players: Vec::new(),
+                    teams: Vec::new(),
+                };
+
+                (id, game)
+            })
+            .collect()
+    }
+
+    fn created_at(map: &Map) -> Vec<i64> {
+        map.iter().map(|(_, game)| game.created_at / 1000).collect()
+    }
+
+    #[test]
+    fn overlapping_pages_leave_no_gap() {
+        let mut map = page(1..=10);
+
+        assert_eq!(map.merge(page(6..=15)), None);
+        assert_eq!(created_at(&map), (1..=15).rev().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn partial_pages_leave_no_gap() {
+        let mut map = page(1..=10);
+
+        // Fewer games than a page are all the games played since
+        assert_eq!(map.merge(page(20..=22)), None);
+        assert_eq!(map.len(), 13);
+    }
+
+    #[test]
+    fn gap_pages_arriving_after_newer_latest_pages() {
+        let mut map = page(1..=10);
+
+        assert_eq!(map.merge(page(16..=25)), Some(Cursor::Before(16)));
+
+        // Newer games are played before the gap is filled
+        assert_eq!(map.merge(page(21..=30)), None);
+        assert_eq!(map.merge(page(6..=15)), None);
+
+        assert_eq!(created_at(&map), (1..=30).rev().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn gaps_are_filled_page_by_page() {
+        let mut map = page(1..=10);
+
+        assert_eq!(map.merge(page(31..=40)), Some(Cursor::Before(31)));
+        assert_eq!(map.merge(page(21..=30)), Some(Cursor::Before(21)));
+        // Adjacent pages cannot be told apart from a gap, so one more page is requested
+        assert_eq!(map.merge(page(11..=20)), Some(Cursor::Before(11)));
+        assert_eq!(map.merge(page(1..=10)), None);
+
+        assert_eq!(map.len(), 40);
+    }
+
+    #[test]
+    fn merging_keeps_known_games() {
+        let mut map = page(1..=30);
+
+        assert_eq!(map.merge(page(5..=7)), None);
+        assert_eq!(map.len(), 30);
+        assert_eq!(map.newest().map(|game| game.created_at), Some(30_000));
+        assert_eq!(map.oldest().map(|game| game.created_at), Some(1_000));
+    }
+}
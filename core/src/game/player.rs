@@ -18,6 +18,8 @@ pub struct Player {
     pub summoner_spells: SummonerSpells,
     pub rune_page: rune::Page,
     pub stats: Stats,
+    /// The subteam, placement and augments of the player, in Arena games.
+    pub arena: Option<Arena>,
 }
 
 impl Player {
@@ -75,16 +77,35 @@ impl Player {
                 kills: 14,
                 deaths: 4,
                 assists: 6,
+                first_blood: FirstBlood::Assist,
+                largest_killing_spree: 6,
+                double_kills: 2,
+                triple_kills: 1,
+                quadra_kills: 0,
+                penta_kills: 0,
                 creep_score: 33,
                 monster_score: 199,
                 vision_score: 36,
                 damage_dealt: 27476,
+                physical_damage_dealt: 19880,
+                magic_damage_dealt: 5102,
+                true_damage_dealt: 2494,
+                objective_damage: 31210,
+                turret_kills: 2,
                 damage_taken: 39444,
+                damage_mitigated: 28120,
+                healing: 9843,
+                ally_healing: 0,
+                ally_shielding: 0,
+                crowd_control: 21,
+                time_dead: 142,
                 gold: 17258,
+                gold_spent: 16400,
                 control_wards: 4,
                 wards_placed: 11,
                 wards_removed: 2,
             },
+            arena: None,
         }
     }
 }
@@ -110,19 +131,67 @@ impl From<&riven::models::match_v5::Participant> for Player {
             kills: participant.kills as u32,
             deaths: participant.deaths as u32,
             assists: participant.assists as u32,
+            first_blood: if participant.first_blood_kill {
+                FirstBlood::Kill
+            } else if participant.first_blood_assist {
+                FirstBlood::Assist
+            } else {
+                FirstBlood::None
+            },
+            largest_killing_spree: participant.largest_killing_spree as u32,
+            double_kills: participant.double_kills as u32,
+            triple_kills: participant.triple_kills as u32,
+            quadra_kills: participant.quadra_kills as u32,
+            penta_kills: participant.penta_kills as u32,
+
             creep_score: participant.total_minions_killed as u32,
             monster_score: participant.neutral_minions_killed as u32,
             vision_score: participant.vision_score as u32,
 
             damage_dealt: participant.total_damage_dealt_to_champions as u32,
+            physical_damage_dealt: participant.physical_damage_dealt_to_champions as u32,
+            magic_damage_dealt: participant.magic_damage_dealt_to_champions as u32,
+            true_damage_dealt: participant.true_damage_dealt_to_champions as u32,
+            objective_damage: participant.damage_dealt_to_objectives as u32,
+            turret_kills: participant.turret_kills as u32,
             damage_taken: participant.total_damage_taken as u32,
+            damage_mitigated: participant.damage_self_mitigated as u32,
+
+            healing: participant.total_heal as u32,
+            ally_healing: participant.total_heals_on_teammates as u32,
+            ally_shielding: participant.total_damage_shielded_on_teammates as u32,
+            crowd_control: participant.time_c_cing_others as u32,
+            time_dead: participant.total_time_spent_dead as u32,
+
             gold: participant.gold_earned as u32,
+            gold_spent: participant.gold_spent as u32,
 
             control_wards: participant.vision_wards_bought_in_game as u32,
             wards_placed: participant.wards_placed as u32,
             wards_removed: participant.wards_killed as u32,
         };
 
+        let arena = participant
+            .player_subteam_id
+            .filter(|subteam| *subteam > 0)
+            .map(|subteam| Arena {
+                subteam: subteam as u8,
+                placement: participant.subteam_placement.unwrap_or_default() as u8,
+                augments: [
+                    participant.player_augment1,
+                    participant.player_augment2,
+                    participant.player_augment3,
+                    participant.player_augment4,
+                    participant.player_augment5,
+                    participant.player_augment6,
+                ]
+                .into_iter()
+                .flatten()
+                .filter(|augment| *augment > 0)
+                .map(|augment| Augment(augment as u32))
+                .collect(),
+            });
+
         let result = if participant.game_ended_in_early_surrender {
             game::Result::Remake
         } else if participant.game_ended_in_surrender {
@@ -152,31 +221,79 @@ impl From<&riven::models::match_v5::Participant> for Player {
             ]),
             rune_page: rune::Page::from(participant.perks.clone()),
             stats,
+            arena,
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, bitcode::Encode, bitcode::Decode)]
+#[derive(Debug, Clone, Copy, Default, bitcode::Encode, bitcode::Decode)]
 pub struct Stats {
     pub level: u32,
 
     pub kills: u32,
     pub deaths: u32,
     pub assists: u32,
+    pub first_blood: FirstBlood,
+    pub largest_killing_spree: u32,
+    pub double_kills: u32,
+    pub triple_kills: u32,
+    pub quadra_kills: u32,
+    pub penta_kills: u32,
 
     pub creep_score: u32,
     pub monster_score: u32,
     pub vision_score: u32,
 
+    /// Damage dealt to champions.
     pub damage_dealt: u32,
+    pub physical_damage_dealt: u32,
+    pub magic_damage_dealt: u32,
+    pub true_damage_dealt: u32,
+    /// Damage dealt to buildings and epic monsters.
+    pub objective_damage: u32,
+    pub turret_kills: u32,
     pub damage_taken: u32,
+    pub damage_mitigated: u32,
+
+    pub healing: u32,
+    pub ally_healing: u32,
+    pub ally_shielding: u32,
+    /// Seconds spent crowd controlling enemies.
+    pub crowd_control: u32,
+    /// Seconds spent dead.
+    pub time_dead: u32,
+
     pub gold: u32,
+    pub gold_spent: u32,
 
     pub control_wards: u32,
     pub wards_placed: u32,
     pub wards_removed: u32,
 }
 
+/// The part a player took in the first kill of the game.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, bitcode::Encode, bitcode::Decode,
+)]
+pub enum FirstBlood {
+    #[default]
+    None,
+    Assist,
+    Kill,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, bitcode::Encode, bitcode::Decode)]
+pub struct Arena {
+    /// The subteam of the player, from 1 to 8.
+    pub subteam: u8,
+    /// The placement of the subteam, from 1 to 8.
+    pub placement: u8,
+    pub augments: Vec<Augment>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, bitcode::Encode, bitcode::Decode)]
+pub struct Augment(pub u32);
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, bitcode::Encode, bitcode::Decode)]
 pub struct SummonerSpell(u32);
 
@@ -0,0 +1,145 @@
+use crate::game::{self, Player};
+use crate::{Champion, Team as Side};
+
+use riven::models::match_v5;
+
+#[derive(Debug, Clone, bitcode::Encode, bitcode::Decode)]
+pub struct Team {
+    pub id: Side,
+    pub result: game::Result,
+    pub bans: Vec<Ban>,
+    pub objectives: Objectives,
+}
+
+impl Team {
+    /// The teams of the `players`, for games that came without team data.
+    pub(crate) fn from_players(players: &[Player]) -> Vec<Team> {
+        let mut teams: Vec<Team> = Vec::new();
+
+        for player in players {
+            if teams.iter().all(|team| team.id != player.team) {
+                teams.push(Team {
+                    id: player.team,
+                    result: player.result,
+                    bans: Vec::new(),
+                    objectives: Objectives::default(),
+                });
+            }
+        }
+
+        teams
+    }
+
+    #[cfg(feature = "dummy")]
+    pub fn dummy(id: Side, result: game::Result) -> Self {
+        let won = result.won();
+        let objective = |kills: u32, first: bool| Objective {
+            kills: if won { kills } else { kills / 3 },
+            first: first == won,
+        };
+
+        let (champions, first_turn) = if id == Side::BLUE {
+            ([157, 238, 91, 84, 555], 1)
+        } else {
+            ([266, 103, 136, 518, 145], 6)
+        };
+        let bans = champions
+            .into_iter()
+            .zip(first_turn..)
+            .map(|(champion, pick_turn)| Ban {
+                champion: Champion(champion),
+                pick_turn,
+            })
+            .collect();
+
+        Team {
+            id,
+            result,
+            bans,
+            objectives: Objectives {
+                champion: objective(31, true),
+                tower: objective(9, true),
+                inhibitor: objective(2, true),
+                dragon: objective(4, false),
+                baron: objective(1, true),
+                herald: objective(1, true),
+                grubs: objective(3, false),
+            },
+        }
+    }
+}
+
+impl From<&match_v5::Team> for Team {
+    fn from(team: &match_v5::Team) -> Self {
+        let bans = team
+            .bans
+            .iter()
+            // Skipped bans are sent as a champion with a negative id
+            .filter(|ban| ban.champion_id.0 >= 0)
+            .map(|ban| Ban {
+                champion: Champion::from(ban.champion_id),
+                pick_turn: ban.pick_turn as u8,
+            })
+            .collect();
+
+        Team {
+            id: Side(team.team_id as usize),
+            result: game::Result::from(team.win),
+            bans,
+            objectives: Objectives::from(&team.objectives),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, bitcode::Encode, bitcode::Decode)]
+pub struct Ban {
+    pub champion: Champion,
+    /// The pick turn of the player who banned the champion, from 1 to 10.
+    pub pick_turn: u8,
+}
+
+#[derive(Debug, Clone, Copy, Default, bitcode::Encode, bitcode::Decode)]
+pub struct Objectives {
+    pub champion: Objective,
+    pub tower: Objective,
+    pub inhibitor: Objective,
+    pub dragon: Objective,
+    pub baron: Objective,
+    pub herald: Objective,
+    /// Void grubs, only present in games since they were introduced.
+    pub grubs: Objective,
+}
+
+impl From<&match_v5::Objectives> for Objectives {
+    fn from(objectives: &match_v5::Objectives) -> Self {
+        Objectives {
+            champion: Objective::from(&objectives.champion),
+            tower: Objective::from(&objectives.tower),
+            inhibitor: Objective::from(&objectives.inhibitor),
+            dragon: Objective::from(&objectives.dragon),
+            baron: Objective::from(&objectives.baron),
+            herald: Objective::from(&objectives.rift_herald),
+            grubs: objectives
+                .horde
+                .as_ref()
+                .map(Objective::from)
+                .unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, bitcode::Encode, bitcode::Decode)]
+pub struct Objective {
+    pub kills: u32,
+    /// Whether the team took the first of these objectives.
+    pub first: bool,
+}
+
+impl From<&match_v5::Objective> for Objective {
+    fn from(objective: &match_v5::Objective) -> Self {
+        Objective {
+            kills: objective.kills as u32,
+            first: objective.first,
+        }
+    }
+}
@@ -0,0 +1,865 @@
+use crate::codec::{self, DecodeError, Payload};
+use crate::game::{self, Item, RequestError};
+use crate::{Client, Region, Team};
+
+use riven::models::match_v5;
+
+/// A participant of a game, numbered from 1 in the order of [`game::Game::players`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, bitcode::Encode, bitcode::Decode)]
+pub struct Participant(pub u8);
+
+impl Participant {
+    fn from_id(id: i32) -> Option<Self> {
+        u8::try_from(id).ok().filter(|id| *id > 0).map(Self)
+    }
+
+    /// The index of the participant in [`game::Game::players`].
+    pub fn index(&self) -> usize {
+        self.0 as usize - 1
+    }
+}
+
+/// A position on the map, in game units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, bitcode::Encode, bitcode::Decode)]
+pub struct Position {
+    pub x: u16,
+    pub y: u16,
+}
+
+impl From<&match_v5::Position> for Position {
+    fn from(position: &match_v5::Position) -> Self {
+        Self {
+            x: position.x.clamp(0, u16::MAX as i32) as u16,
+            y: position.y.clamp(0, u16::MAX as i32) as u16,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, bitcode::Encode, bitcode::Decode)]
+pub struct Stats {
+    /// Total gold earned so far.
+    pub gold: u32,
+    pub xp: u32,
+    /// Lane minions and jungle monsters killed so far.
+    pub creep_score: u16,
+    pub level: u8,
+    pub position: Position,
+}
+
+/// A snapshot of every participant, taken once per frame interval (usually a minute).
+#[derive(Debug, Clone, bitcode::Encode, bitcode::Decode)]
+pub struct Frame {
+    /// Milliseconds since the game started.
+    pub timestamp: u32,
+    /// Indexed by [`Participant::index`].
+    pub players: Vec<Stats>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, bitcode::Encode, bitcode::Decode)]
+pub enum Ward {
+    Yellow,
+    Blue,
+    Control,
+    Sight,
+    Other,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, bitcode::Encode, bitcode::Decode)]
+pub enum ItemAction {
+    Purchased,
+    Sold,
+    Destroyed,
+    /// The purchase of the item was undone.
+    Undone,
+    /// The sale of the item was undone, giving it back.
+    SaleUndone,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, bitcode::Encode, bitcode::Decode)]
+pub enum Objective {
+    Dragon,
+    ElderDragon,
+    Baron,
+    Herald,
+    VoidGrub,
+    Atakhan,
+    Tower,
+    Inhibitor,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, bitcode::Encode, bitcode::Decode)]
+pub enum Skill {
+    Q,
+    W,
+    E,
+    R,
+}
+
+#[derive(Debug, Clone, bitcode::Encode, bitcode::Decode)]
+pub enum Event {
+    Kill {
+        /// `None` when executed by minions, monsters or towers.
+        killer: Option<Participant>,
+        victim: Participant,
+        assists: Vec<Participant>,
+        position: Position,
+    },
+    Ward {
+        participant: Participant,
+        ward: Ward,
+        /// Whether the ward was placed or destroyed by the participant.
+        placed: bool,
+    },
+    Item {
+        participant: Participant,
+        item: Item,
+        action: ItemAction,
+    },
+    Objective {
+        killer: Option<Participant>,
+        /// The team that took the objective.
+        team: Team,
+        objective: Objective,
+        position: Option<Position>,
+    },
+    SkillLevelUp {
+        participant: Participant,
+        skill: Skill,
+    },
+}
+
+#[derive(Debug, Clone, bitcode::Encode, bitcode::Decode)]
+pub struct TimedEvent {
+    /// Milliseconds since the game started.
+    pub timestamp: u32,
+    pub event: Event,
+}
+
+/// An item bought by a participant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Purchase {
+    /// Milliseconds since the game started.
+    pub timestamp: u32,
+    pub item: Item,
+    /// When the item was sold, if it was.
+    pub sold_at: Option<u32>,
+}
+
+/// The build path and skill order of a participant, in chronological order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Build {
+    pub items: Vec<Purchase>,
+    pub skills: Vec<Skill>,
+}
+
+#[derive(Debug, Clone, bitcode::Encode, bitcode::Decode)]
+pub struct Timeline {
+    pub id: game::Id,
+    /// The puuid of each participant, indexed by [`Participant::index`].
+    pub participants: Vec<String>,
+    pub frames: Vec<Frame>,
+    pub events: Vec<TimedEvent>,
+}
+
+impl Timeline {
+    pub async fn fetch(
+        client: &Client,
+        region: Region,
+        id: &game::Id,
+    ) -> Result<Self, RequestError> {
+        client
+            .as_ref()
+            .match_v5()
+            .get_timeline(region.into(), id.as_ref())
+            .await?
+            .map(Timeline::from)
+            .ok_or(RequestError::NotFound)
+    }
+
+    #[cfg(feature = "dummy")]
+    pub fn dummy(id: game::Id) -> Self {
+        const PLAYERS: u32 = 10;
+        const MINUTES: u32 = 37;
+
+        let frames = (0..MINUTES)
+            .map(|minute| Frame {
+                timestamp: minute * 60_000,
+                players: (0..PLAYERS)
+                    .map(|player| {
+                        // The first five players win the dummy game, so they get ahead
+                        let pace = if player < 5 { 420 } else { 380 } + player * 7;
+                        let angle = (minute + player * 3) as f32 / 6.0;
+
+                        Stats {
+                            gold: 500 + minute * pace,
+                            xp: minute * (pace + 40),
+                            creep_score: (minute * (5 + player % 4)) as u16,
+                            level: (1 + minute / 2).min(18) as u8,
+                            position: Position {
+                                x: (7400.0 + 5000.0 * angle.cos()) as u16,
+                                y: (7400.0 + 5000.0 * angle.sin()) as u16,
+                            },
+                        }
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        const ITEMS: [(u32, usize); 7] = [
+            (0, 3364),
+            (9, 3161),
+            (15, 6692),
+            (21, 3156),
+            (26, 6333),
+            (31, 3174),
+            (35, 6695),
+        ];
+        const SKILLS: [Skill; 18] = [
+            Skill::Q,
+            Skill::W,
+            Skill::E,
+            Skill::Q,
+            Skill::Q,
+            Skill::R,
+            Skill::Q,
+            Skill::W,
+            Skill::Q,
+            Skill::W,
+            Skill::R,
+            Skill::W,
+            Skill::W,
+            Skill::E,
+            Skill::E,
+            Skill::R,
+            Skill::E,
+            Skill::E,
+        ];
+
+        let builds = (0..PLAYERS).flat_map(|player| {
+            let participant = Participant(player as u8 + 1);
+            let items = ITEMS.iter().map(move |(minute, item)| TimedEvent {
+                timestamp: minute * 60_000 + player * 1_000,
+                event: Event::Item {
+                    participant,
+                    item: Item(*item),
+                    action: ItemAction::Purchased,
+                },
+            });
+            // Levels go up every two minutes in the frames above
+            let skills = SKILLS
+                .iter()
+                .enumerate()
+                .map(move |(level, skill)| TimedEvent {
+                    timestamp: level as u32 * 120_000 + 1_000,
+                    event: Event::SkillLevelUp {
+                        participant,
+                        skill: *skill,
+                    },
+                });
+
+            items.chain(skills)
+        });
+
+        let mut events = (1..MINUTES)
+            .flat_map(|minute| {
+                let killer = Participant((minute % PLAYERS) as u8 + 1);
+                let victim = Participant(((minute + 5) % PLAYERS) as u8 + 1);
+                let team = if killer.0 <= 5 { Team::RED } else { Team::BLUE };
+                let timestamp = minute * 60_000 + 30_000;
+
+                let mut events = vec![
+                    TimedEvent {
+                        timestamp,
+                        event: Event::Kill {
+                            killer: Some(killer),
+                            victim,
+                            assists: vec![Participant(((minute + 1) % PLAYERS) as u8 + 1)],
+                            position: Position {
+                                x: (minute * 397 % 14000) as u16,
+                                y: (minute * 677 % 14000) as u16,
+                            },
+                        },
+                    },
+                    TimedEvent {
+                        timestamp: timestamp + 5_000,
+                        event: Event::Ward {
+                            participant: victim,
+                            ward: Ward::Yellow,
+                            placed: true,
+                        },
+                    },
+                ];
+
+                if minute % 6 == 0 {
+                    events.push(TimedEvent {
+                        timestamp: timestamp + 10_000,
+                        event: Event::Objective {
+                            killer: Some(killer),
+                            team,
+                            objective: if minute < 25 {
+                                Objective::Dragon
+                            } else {
+                                Objective::Baron
+                            },
+                            position: Some(if minute < 25 {
+                                Position { x: 9866, y: 4414 }
+                            } else {
+                                Position { x: 5007, y: 10471 }
+                            }),
+                        },
+                    });
+                }
+
+                events
+            })
+            .chain(builds)
+            .collect::<Vec<_>>();
+
+        events.sort_by_key(|event| event.timestamp);
+
+        // Dummy game ids end with the puuid of the summoner, made of its riot id
+        let riot_id = id
+            .as_ref()
+            .strip_prefix("dummy-")
+            .and_then(|puuid| puuid.rsplit_once('-'))
+            .map(|(name, tagline)| crate::account::RiotId::new(name, tagline))
+            .unwrap_or_else(|| crate::account::RiotId::new("foo", "bar"));
+        let participants = game::Game::dummy(riot_id)
+            .players
+            .into_iter()
+            .map(|player| player.puuid)
+            .collect();
+
+        Self {
+            id,
+            participants,
+            frames,
+            events,
+        }
+    }
+
+    /// The position of a participant at `timestamp`, interpolated between the closest frames.
+    ///
+    /// Some events (e.g. ward placements) have no position, so this is the best estimate.
+    pub fn position(&self, participant: Participant, timestamp: u32) -> Option<Position> {
+        let position = |frame: &Frame| {
+            frame
+                .players
+                .get(participant.index())
+                .map(|stats| stats.position)
+        };
+
+        let next = self
+            .frames
+            .iter()
+            .position(|frame| frame.timestamp >= timestamp);
+
+        let (before, after) = match next {
+            Some(0) => return self.frames.first().and_then(position),
+            None => return self.frames.last().and_then(position),
+            Some(next) => (&self.frames[next - 1], &self.frames[next]),
+        };
+
+        let (from, to) = (position(before)?, position(after)?);
+        let progress = (timestamp - before.timestamp) as f32
+            / (after.timestamp - before.timestamp).max(1) as f32;
+        let lerp = |from: u16, to: u16| (from as f32 + (to as f32 - from as f32) * progress) as u16;
+
+        Some(Position {
+            x: lerp(from.x, to.x),
+            y: lerp(from.y, to.y),
+        })
+    }
+
+    /// Derives the build of a participant from its item and skill events.
+    ///
+    /// Undone purchases are left out and sold items are kept, with the time they were sold at
+    /// unless the sale was undone.
+    pub fn build(&self, participant: Participant) -> Build {
+        let mut build = Build::default();
+
+        for timed in &self.events {
+            match timed.event {
+                Event::Item {
+                    participant: buyer,
+                    item,
+                    action,
+                } if buyer == participant => {
+                    let last = build
+                        .items
+                        .iter()
+                        .rposition(|purchase| purchase.item == item && purchase.sold_at.is_none());
+
+                    match action {
+                        ItemAction::Purchased => build.items.push(Purchase {
+                            timestamp: timed.timestamp,
+                            item,
+                            sold_at: None,
+                        }),
+                        ItemAction::Sold => {
+                            if let Some(last) = last {
+                                build.items[last].sold_at = Some(timed.timestamp);
+                            }
+                        }
+                        ItemAction::Undone => {
+                            if let Some(last) = last {
+                                build.items.remove(last);
+                            }
+                        }
+                        ItemAction::SaleUndone => {
+                            if let Some(sold) = build
+                                .items
+                                .iter_mut()
+                                .filter(|purchase| purchase.item == item)
+                                .max_by_key(|purchase| purchase.sold_at)
+                            {
+                                sold.sold_at = None;
+                            }
+                        }
+                        ItemAction::Destroyed => {}
+                    }
+                }
+                Event::SkillLevelUp {
+                    participant: player,
+                    skill,
+                } if player == participant => build.skills.push(skill),
+                _ => {}
+            }
+        }
+
+        build
+    }
+
+    pub fn participant(&self, puuid: &str) -> Option<Participant> {
+        self.participants
+            .iter()
+            .position(|participant| participant == puuid)
+            .and_then(|index| Participant::from_id(index as i32 + 1))
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        codec::encode(self)
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self, DecodeError> {
+        codec::decode(bytes)
+    }
+}
+
+impl Payload for Timeline {
+    const KIND: codec::Kind = codec::Kind::Timeline;
+    /// Older timelines are not migrated, as they can be requested again.
+    const VERSION: u16 = 3;
+}
+
+impl From<match_v5::Timeline> for Timeline {
+    fn from(timeline: match_v5::Timeline) -> Self {
+        let participants = timeline.metadata.participants;
+        let size = participants.len();
+
+        let frames = timeline
+            .info
+            .frames
+            .iter()
+            .map(|frame| {
+                let mut players = vec![Stats::default(); size];
+
+                for (id, stats) in frame.participant_frames.iter().flatten() {
+                    let Some(player) = Participant::from_id(*id)
+                        .and_then(|participant| players.get_mut(participant.index()))
+                    else {
+                        continue;
+                    };
+
+                    *player = Stats {
+                        gold: stats.total_gold.max(0) as u32,
+                        xp: stats.xp.max(0) as u32,
+                        creep_score: (stats.minions_killed + stats.jungle_minions_killed).max(0)
+                            as u16,
+                        level: stats.level.max(0) as u8,
+                        position: Position::from(&stats.position),
+                    };
+                }
+
+                Frame {
+                    timestamp: frame.timestamp.max(0) as u32,
+                    players,
+                }
+            })
+            .collect();
+
+        let events = timeline
+            .info
+            .frames
+            .iter()
+            .flat_map(|frame| &frame.events)
+            .filter_map(|event| {
+                Some(TimedEvent {
+                    timestamp: event.timestamp.max(0) as u32,
+                    event: Event::try_from(event).ok()?,
+                })
+            })
+            .collect();
+
+        Self {
+            id: game::Id(timeline.metadata.match_id),
+            participants,
+            frames,
+            events,
+        }
+    }
+}
+
+impl TryFrom<&match_v5::EventsTimeLine> for Event {
+    type Error = ();
+
+    fn try_from(event: &match_v5::EventsTimeLine) -> Result<Self, Self::Error> {
+        let participant = || {
+            event
+                .participant_id
+                .and_then(Participant::from_id)
+                .ok_or(())
+        };
+        let killer = event.killer_id.and_then(Participant::from_id);
+        let position = event.position.as_ref().map(Position::from);
+
+        let item = |action| {
+            Ok(Event::Item {
+                participant: participant()?,
+                item: event.item_id.ok_or(()).and_then(Item::try_from)?,
+                action,
+            })
+        };
+
+        match event.r#type.as_str() {
+            "CHAMPION_KILL" => Ok(Event::Kill {
+                killer,
+                victim: event.victim_id.and_then(Participant::from_id).ok_or(())?,
+                assists: event
+                    .assisting_participant_ids
+                    .iter()
+                    .flatten()
+                    .filter_map(|id| Participant::from_id(*id))
+                    .collect(),
+                position: position.unwrap_or_default(),
+            }),
+            "WARD_PLACED" => Ok(Event::Ward {
+                participant: event.creator_id.and_then(Participant::from_id).ok_or(())?,
+                ward: ward(event.ward_type.as_deref()),
+                placed: true,
+            }),
+            "WARD_KILL" => Ok(Event::Ward {
+                participant: killer.ok_or(())?,
+                ward: ward(event.ward_type.as_deref()),
+                placed: false,
+            }),
+            "ITEM_PURCHASED" => item(ItemAction::Purchased),
+            "ITEM_SOLD" => item(ItemAction::Sold),
+            "ITEM_DESTROYED" => item(ItemAction::Destroyed),
+            // Undoing a sale leaves nothing before the undo, the item comes back after it
+            "ITEM_UNDO" if event.before_id == Some(0) => Ok(Event::Item {
+                participant: participant()?,
+                item: event.after_id.ok_or(()).and_then(Item::try_from)?,
+                action: ItemAction::SaleUndone,
+            }),
+            "ITEM_UNDO" => Ok(Event::Item {
+                participant: participant()?,
+                item: event.before_id.ok_or(()).and_then(Item::try_from)?,
+                action: ItemAction::Undone,
+            }),
+            "ELITE_MONSTER_KILL" => {
+                let objective = match event.monster_type.as_deref() {
+                    Some("DRAGON") if event.monster_sub_type.as_deref() == Some("ELDER_DRAGON") => {
+                        Objective::ElderDragon
+                    }
+                    Some("DRAGON") => Objective::Dragon,
+                    Some("BARON_NASHOR") => Objective::Baron,
+                    Some("RIFTHERALD") => Objective::Herald,
+                    Some("HORDE") => Objective::VoidGrub,
+                    Some("ATAKHAN") => Objective::Atakhan,
+                    _ => return Err(()),
+                };
+
+                Ok(Event::Objective {
+                    killer,
+                    team: Team(event.killer_team_id.ok_or(())? as usize),
+                    objective,
+                    position,
+                })
+            }
+            "BUILDING_KILL" => {
+                let objective = match event.building_type.as_deref() {
+                    Some("TOWER_BUILDING") => Objective::Tower,
+                    Some("INHIBITOR_BUILDING") => Objective::Inhibitor,
+                    _ => return Err(()),
+                };
+
+                // `team_id` is the team that owned the building
+                let team = match Team(event.team_id.ok_or(())? as usize) {
+                    Team::BLUE => Team::RED,
+                    _ => Team::BLUE,
+                };
+
+                Ok(Event::Objective {
+                    killer,
+                    team,
+                    objective,
+                    position,
+                })
+            }
+            "SKILL_LEVEL_UP" if event.level_up_type.as_deref() == Some("NORMAL") => {
+                let skill = match event.skill_slot {
+                    Some(1) => Skill::Q,
+                    Some(2) => Skill::W,
+                    Some(3) => Skill::E,
+                    Some(4) => Skill::R,
+                    _ => return Err(()),
+                };
+
+                Ok(Event::SkillLevelUp {
+                    participant: participant()?,
+                    skill,
+                })
+            }
+            _ => Err(()),
+        }
+    }
+}
+
+fn ward(kind: Option<&str>) -> Ward {
+    match kind {
+        Some("YELLOW_TRINKET") => Ward::Yellow,
+        Some("BLUE_TRINKET") => Ward::Blue,
+        Some("CONTROL_WARD") => Ward::Control,
+        Some("SIGHT_WARD") => Ward::Sight,
+        _ => Ward::Other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOOTS: Item = Item(1001);
+    const LONG_SWORD: Item = Item(1036);
+    const POTION: Item = Item(2003);
+    const SERRATED_DIRK: Item = Item(3134);
+
+    fn timeline(events: Vec<(u32, Event)>) -> Timeline {
+        Timeline {
+            id: game::Id(String::from("BR1_1")),
+            participants: vec![String::from("first"), String::from("second")],
+            frames: Vec::new(),
+            events: events
+                .into_iter()
+                .map(|(timestamp, event)| TimedEvent { timestamp, event })
+                .collect(),
+        }
+    }
+
+    fn item(participant: u8, item: Item, action: ItemAction) -> Event {
+        Event::Item {
+            participant: Participant(participant),
+            item,
+            action,
+        }
+    }
+
+    fn purchase(timestamp: u32, item: Item, sold_at: Option<u32>) -> Purchase {
+        Purchase {
+            timestamp,
+            item,
+            sold_at,
+        }
+    }
+
+    fn frame(timestamp: u32, positions: &[(u16, u16)]) -> Frame {
+        Frame {
+            timestamp,
+            players: positions
+                .iter()
+                .map(|(x, y)| Stats {
+                    position: Position { x: *x, y: *y },
+                    ..Stats::default()
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn undone_purchases_are_left_out() {
+        let timeline = timeline(vec![
+            (1_000, item(1, BOOTS, ItemAction::Purchased)),
+            (2_000, item(1, POTION, ItemAction::Purchased)),
+            (3_000, item(1, POTION, ItemAction::Purchased)),
+            (4_000, item(1, POTION, ItemAction::Undone)),
+            (5_000, item(1, LONG_SWORD, ItemAction::Purchased)),
+            (6_000, item(1, LONG_SWORD, ItemAction::Undone)),
+        ]);
+
+        assert_eq!(
+            timeline.build(Participant(1)).items,
+            [purchase(1_000, BOOTS, None), purchase(2_000, POTION, None)]
+        );
+    }
+
+    #[test]
+    fn sold_items_are_kept() {
+        let timeline = timeline(vec![
+            (1_000, item(1, LONG_SWORD, ItemAction::Purchased)),
+            (2_000, item(1, LONG_SWORD, ItemAction::Purchased)),
+            (3_000, item(1, LONG_SWORD, ItemAction::Sold)),
+            (4_000, item(1, LONG_SWORD, ItemAction::Sold)),
+            // Nothing left to sell
+            (5_000, item(1, LONG_SWORD, ItemAction::Sold)),
+        ]);
+
+        assert_eq!(
+            timeline.build(Participant(1)).items,
+            [
+                purchase(1_000, LONG_SWORD, Some(4_000)),
+                purchase(2_000, LONG_SWORD, Some(3_000)),
+            ]
+        );
+    }
+
+    #[test]
+    fn undone_sales_give_the_latest_sold_item_back() {
+        let timeline = timeline(vec![
+            (1_000, item(1, LONG_SWORD, ItemAction::Purchased)),
+            (2_000, item(1, LONG_SWORD, ItemAction::Sold)),
+            (3_000, item(1, LONG_SWORD, ItemAction::Purchased)),
+            (4_000, item(1, LONG_SWORD, ItemAction::Sold)),
+            (5_000, item(1, LONG_SWORD, ItemAction::SaleUndone)),
+        ]);
+
+        assert_eq!(
+            timeline.build(Participant(1)).items,
+            [
+                purchase(1_000, LONG_SWORD, Some(2_000)),
+                purchase(3_000, LONG_SWORD, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn consumed_components_stay_in_the_build() {
+        let timeline = timeline(vec![
+            (1_000, item(1, LONG_SWORD, ItemAction::Purchased)),
+            (2_000, item(1, LONG_SWORD, ItemAction::Destroyed)),
+            (2_000, item(1, SERRATED_DIRK, ItemAction::Purchased)),
+        ]);
+
+        assert_eq!(
+            timeline.build(Participant(1)).items,
+            [
+                purchase(1_000, LONG_SWORD, None),
+                purchase(2_000, SERRATED_DIRK, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn builds_are_per_participant() {
+        let timeline = timeline(vec![
+            (1_000, item(1, BOOTS, ItemAction::Purchased)),
+            (1_500, item(2, LONG_SWORD, ItemAction::Purchased)),
+            (2_000, item(2, BOOTS, ItemAction::Sold)),
+            (
+                3_000,
+                Event::SkillLevelUp {
+                    participant: Participant(2),
+                    skill: Skill::E,
+                },
+            ),
+            (
+                4_000,
+                Event::SkillLevelUp {
+                    participant: Participant(1),
+                    skill: Skill::Q,
+                },
+            ),
+            (
+                5_000,
+                Event::SkillLevelUp {
+                    participant: Participant(1),
+                    skill: Skill::W,
+                },
+            ),
+        ]);
+
+        assert_eq!(
+            timeline.build(Participant(1)),
+            Build {
+                items: vec![purchase(1_000, BOOTS, None)],
+                skills: vec![Skill::Q, Skill::W],
+            }
+        );
+        assert_eq!(
+            timeline.build(Participant(2)),
+            Build {
+                items: vec![purchase(1_500, LONG_SWORD, None)],
+                skills: vec![Skill::E],
+            }
+        );
+    }
+
+    #[test]
+    fn summoners_missing_from_the_timeline_are_not_selected() {
+        let timeline = timeline(vec![(1_000, item(1, BOOTS, ItemAction::Purchased))]);
+
+        assert_eq!(timeline.participant("first"), Some(Participant(1)));
+        assert_eq!(timeline.participant("second"), Some(Participant(2)));
+        assert_eq!(
+            timeline
+                .participant("summoner")
+                .map(|participant| participant.index()),
+            None
+        );
+    }
+
+    #[test]
+    fn positions_are_interpolated_between_frames() {
+        let mut timeline = timeline(Vec::new());
+        timeline.frames = vec![
+            frame(0, &[(100, 100), (0, 0)]),
+            frame(60_000, &[(700, 1_300), (0, 0)]),
+        ];
+
+        assert_eq!(
+            timeline.position(Participant(1), 30_000),
+            Some(Position { x: 400, y: 700 })
+        );
+        assert_eq!(
+            timeline.position(Participant(1), 60_000),
+            Some(Position { x: 700, y: 1_300 })
+        );
+        // Past the last frame, the participant stays where it was last seen
+        assert_eq!(
+            timeline.position(Participant(1), 90_000),
+            Some(Position { x: 700, y: 1_300 })
+        );
+    }
+
+    #[test]
+    fn missing_player_frames_have_no_position() {
+        let mut timeline = timeline(Vec::new());
+        timeline.frames = vec![
+            frame(0, &[(100, 100), (200, 200)]),
+            frame(60_000, &[(700, 700)]),
+        ];
+
+        assert_eq!(timeline.position(Participant(2), 30_000), None);
+        assert_eq!(timeline.position(Participant(2), 90_000), None);
+        assert_eq!(
+            timeline.position(Participant(2), 0),
+            Some(Position { x: 200, y: 200 })
+        );
+        assert_eq!(timeline.position(Participant(3), 0), None);
+    }
+
+    #[test]
+    fn positions_need_frames() {
+        assert_eq!(timeline(Vec::new()).position(Participant(1), 0), None);
+    }
+}
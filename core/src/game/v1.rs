@@ -0,0 +1,114 @@
+//! The layout of games before [`Stats`] were extended, kept to migrate cached games.
+use crate::account;
+use crate::game::player::{self, Stats, SummonerSpells};
+use crate::game::{self, Id, Queue, TimeId, item, rune};
+use crate::{Champion, Team};
+
+use std::collections::BTreeMap;
+
+#[derive(bitcode::Decode)]
+pub struct Map(BTreeMap<TimeId, Game>);
+
+impl From<Map> for game::Map {
+    fn from(map: Map) -> Self {
+        game::Map(
+            map.0
+                .into_iter()
+                .map(|(id, game)| (id, game.into()))
+                .collect(),
+        )
+    }
+}
+
+#[derive(bitcode::Decode)]
+pub struct Game {
+    id: Id,
+    queue: Queue,
+    created_at: i64,
+    duration: i64,
+    players: Vec<Player>,
+}
+
+impl From<Game> for game::Game {
+    fn from(game: Game) -> Self {
+        let players: Vec<player::Player> =
+            game.players.into_iter().map(player::Player::from).collect();
+
+        Self {
+            id: game.id,
+            queue: game.queue,
+            created_at: game.created_at,
+            duration: game.duration,
+            teams: game::Team::from_players(&players),
+            players,
+        }
+    }
+}
+
+#[derive(bitcode::Decode)]
+struct Player {
+    puuid: String,
+    riot_id: account::RiotId,
+    team: Team,
+    result: game::Result,
+    role: Option<game::Role>,
+    inventory: item::Inventory,
+    trinket: item::Trinket,
+    champion: Champion,
+    summoner_spells: SummonerSpells,
+    rune_page: rune::Page,
+    stats: PlayerStats,
+}
+
+impl From<Player> for player::Player {
+    fn from(player: Player) -> Self {
+        let stats = player.stats;
+
+        Self {
+            puuid: player.puuid,
+            riot_id: player.riot_id,
+            team: player.team,
+            result: player.result,
+            role: player.role,
+            inventory: player.inventory,
+            trinket: player.trinket,
+            champion: player.champion,
+            summoner_spells: player.summoner_spells,
+            rune_page: player.rune_page,
+            stats: Stats {
+                level: stats.level,
+                kills: stats.kills,
+                deaths: stats.deaths,
+                assists: stats.assists,
+                creep_score: stats.creep_score,
+                monster_score: stats.monster_score,
+                vision_score: stats.vision_score,
+                damage_dealt: stats.damage_dealt,
+                damage_taken: stats.damage_taken,
+                gold: stats.gold,
+                control_wards: stats.control_wards,
+                wards_placed: stats.wards_placed,
+                wards_removed: stats.wards_removed,
+                ..Stats::default()
+            },
+            arena: None,
+        }
+    }
+}
+
+#[derive(bitcode::Decode)]
+struct PlayerStats {
+    level: u32,
+    kills: u32,
+    deaths: u32,
+    assists: u32,
+    creep_score: u32,
+    monster_score: u32,
+    vision_score: u32,
+    damage_dealt: u32,
+    damage_taken: u32,
+    gold: u32,
+    control_wards: u32,
+    wards_placed: u32,
+    wards_removed: u32,
+}
@@ -0,0 +1,43 @@
+//! The layout of games before they stored their [`Team`]s, kept to migrate cached games.
+use crate::game::{self, Id, Queue, Team, TimeId, player, v3};
+
+use std::collections::BTreeMap;
+
+#[derive(bitcode::Decode)]
+pub struct Map(BTreeMap<TimeId, Game>);
+
+impl From<Map> for game::Map {
+    fn from(map: Map) -> Self {
+        game::Map(
+            map.0
+                .into_iter()
+                .map(|(id, game)| (id, game.into()))
+                .collect(),
+        )
+    }
+}
+
+#[derive(bitcode::Decode)]
+pub struct Game {
+    id: Id,
+    queue: Queue,
+    created_at: i64,
+    duration: i64,
+    players: Vec<v3::Player>,
+}
+
+impl From<Game> for game::Game {
+    fn from(game: Game) -> Self {
+        let players: Vec<player::Player> =
+            game.players.into_iter().map(player::Player::from).collect();
+
+        Self {
+            id: game.id,
+            queue: game.queue,
+            created_at: game.created_at,
+            duration: game.duration,
+            teams: Team::from_players(&players),
+            players,
+        }
+    }
+}
@@ -0,0 +1,79 @@
+//! The layout of games before Arena players were modeled, kept to migrate cached games.
+use crate::account;
+use crate::game::player::{self, Stats, SummonerSpells};
+use crate::game::{self, Id, Queue, Team, TimeId, item, rune};
+use crate::{Champion, Team as Side};
+
+use std::collections::BTreeMap;
+
+#[derive(bitcode::Decode)]
+pub struct Map(BTreeMap<TimeId, Game>);
+
+impl From<Map> for game::Map {
+    fn from(map: Map) -> Self {
+        game::Map(
+            map.0
+                .into_iter()
+                .map(|(id, game)| (id, game.into()))
+                .collect(),
+        )
+    }
+}
+
+#[derive(bitcode::Decode)]
+pub struct Game {
+    id: Id,
+    queue: Queue,
+    created_at: i64,
+    duration: i64,
+    players: Vec<Player>,
+    teams: Vec<Team>,
+}
+
+impl From<Game> for game::Game {
+    fn from(game: Game) -> Self {
+        Self {
+            id: game.id,
+            // Arena games were cached as an unnamed queue
+            queue: Queue::from(riven::consts::Queue::from(game.queue)),
+            created_at: game.created_at,
+            duration: game.duration,
+            players: game.players.into_iter().map(player::Player::from).collect(),
+            teams: game.teams,
+        }
+    }
+}
+
+#[derive(bitcode::Decode)]
+pub struct Player {
+    puuid: String,
+    riot_id: account::RiotId,
+    team: Side,
+    result: game::Result,
+    role: Option<game::Role>,
+    inventory: item::Inventory,
+    trinket: item::Trinket,
+    champion: Champion,
+    summoner_spells: SummonerSpells,
+    rune_page: rune::Page,
+    stats: Stats,
+}
+
+impl From<Player> for player::Player {
+    fn from(player: Player) -> Self {
+        Self {
+            puuid: player.puuid,
+            riot_id: player.riot_id,
+            team: player.team,
+            result: player.result,
+            role: player.role,
+            inventory: player.inventory,
+            trinket: player.trinket,
+            champion: player.champion,
+            summoner_spells: player.summoner_spells,
+            rune_page: player.rune_page,
+            stats: player.stats,
+            arena: None,
+        }
+    }
+}
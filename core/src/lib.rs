@@ -1,6 +1,14 @@
 pub mod assets;
 pub use assets::Assets;
 
+pub mod codec;
+pub use codec::DecodeError;
+
+pub mod backend;
+pub use backend::Backend;
+
+pub mod cache;
+
 pub mod client;
 pub use client::Client;
 
@@ -12,6 +20,8 @@ pub use account::Account;
 
 pub mod game;
 pub use game::Game;
+
+pub mod live;
 pub use game::Item;
 pub use game::player::SummonerSpell;
 pub use game::rune::Rune;
@@ -29,6 +39,19 @@ pub enum RequestError {
     NotFound,
     #[error("request failed")]
     RequestFailed(InternalApiError),
+    #[error(transparent)]
+    Decode(#[from] DecodeError),
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+}
+
+/// Malformed user input, as opposed to a valid request for something missing.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ParseError {
+    #[error("invalid riot id `{0}`, expected `name#tagline`")]
+    RiotId(String),
+    #[error("unknown region `{0}`")]
+    Region(String),
 }
 
 impl RequestError {
@@ -37,6 +60,15 @@ impl RequestError {
     }
 }
 
+impl From<game::RequestError> for RequestError {
+    fn from(error: game::RequestError) -> Self {
+        match error {
+            game::RequestError::NotFound => Self::NotFound,
+            game::RequestError::RequestFailed(error) => Self::internal(error),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, bitcode::Encode, bitcode::Decode)]
 pub struct Champion(u32);
 
@@ -46,6 +78,10 @@ impl Champion {
         Self(id)
     }
 
+    pub fn id(&self) -> u32 {
+        self.0
+    }
+
     pub fn identifier(&self) -> Option<&str> {
         // NOTE: Pretty sure this is a `riven` bug,
         // checking https://github.com/RiotGames/developer-relations/issues/7
@@ -104,6 +140,16 @@ impl From<String> for Region {
     }
 }
 
+impl FromStr for Region {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        PlatformRoute::from_str(&value.to_uppercase())
+            .map(Region)
+            .map_err(|_| ParseError::Region(value.to_string()))
+    }
+}
+
 impl std::fmt::Display for Region {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str(&self.0.to_string())
@@ -135,3 +181,9 @@ impl From<Region> for PlatformRoute {
         region.0
     }
 }
+
+impl From<Region> for RegionalRoute {
+    fn from(region: Region) -> Self {
+        region.0.to_regional()
+    }
+}
@@ -0,0 +1,143 @@
+//! Games in progress, as seen by the spectator API.
+use crate::account::RiotId;
+use crate::codec::{self, DecodeError, Payload};
+use crate::game::Queue;
+use crate::game::player::SummonerSpells;
+use crate::{Champion, Client, Region, RequestError, Rune, SummonerSpell, Team};
+
+use riven::models::spectator_v5;
+
+#[derive(Debug, Clone, bitcode::Encode, bitcode::Decode)]
+pub struct Game {
+    pub id: i64,
+    pub queue: Queue,
+    /// When the game started, in milliseconds since the Unix epoch.
+    ///
+    /// It is 0 while the players are still on the loading screen.
+    pub started_at: i64,
+    pub players: Vec<Player>,
+}
+
+#[derive(Debug, Clone, bitcode::Encode, bitcode::Decode)]
+pub struct Player {
+    /// Missing for bots and players hidden by streamer mode.
+    pub puuid: Option<String>,
+    pub riot_id: RiotId,
+    pub team: Team,
+    pub champion: Champion,
+    pub summoner_spells: SummonerSpells,
+    pub keystone: Option<Rune>,
+}
+
+impl Game {
+    /// Fetches the game `puuid` is playing.
+    ///
+    /// The leagues of the players are left out, as they are cached along with the ones of the
+    /// players met in games (see [`crate::summoner::Ranks`]).
+    pub async fn fetch(
+        client: &Client,
+        region: Region,
+        puuid: &str,
+    ) -> Result<Option<Self>, RequestError> {
+        let Some(game) = client
+            .as_ref()
+            .spectator_v5()
+            .get_current_game_info_by_puuid(region.into(), puuid)
+            .await
+            .map_err(RequestError::internal)?
+        else {
+            return Ok(None);
+        };
+
+        let players = game.participants.iter().map(Player::new).collect();
+
+        Ok(Some(Self {
+            id: game.game_id,
+            // Custom games have no queue
+            queue: game.game_queue_config_id.map_or(Queue::Custom, Queue::from),
+            started_at: game.game_start_time,
+            players,
+        }))
+    }
+
+    #[cfg(feature = "dummy")]
+    pub fn dummy(riot_id: RiotId) -> Self {
+        let game = crate::Game::dummy(riot_id);
+        let started_at = time::OffsetDateTime::now_utc() - time::Duration::minutes(14);
+
+        Self {
+            id: 3103140356,
+            queue: game.queue,
+            started_at: (started_at.unix_timestamp_nanos() / 1_000_000) as i64,
+            players: game
+                .players
+                .into_iter()
+                .map(|player| Player {
+                    puuid: Some(player.puuid),
+                    riot_id: player.riot_id,
+                    team: player.team,
+                    champion: player.champion,
+                    summoner_spells: player.summoner_spells,
+                    keystone: Some(player.rune_page.primary.keystone.rune),
+                })
+                .collect(),
+        }
+    }
+
+    /// The time elapsed since the game started, `None` while it is loading.
+    pub fn elapsed(&self, now: time::OffsetDateTime) -> Option<time::Duration> {
+        if self.started_at == 0 {
+            return None;
+        }
+
+        let started_at =
+            time::OffsetDateTime::from_unix_timestamp_nanos(self.started_at as i128 * 1_000_000)
+                .ok()?;
+
+        Some((now - started_at).max(time::Duration::ZERO))
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        codec::encode(self)
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self, DecodeError> {
+        codec::decode(bytes)
+    }
+}
+
+impl Payload for Game {
+    const KIND: codec::Kind = codec::Kind::LiveGame;
+    const VERSION: u16 = 2;
+}
+
+impl Player {
+    fn new(participant: &spectator_v5::CurrentGameParticipant) -> Self {
+        let riot_id = participant
+            .riot_id
+            .as_deref()
+            .and_then(|riot_id| riot_id.parse().ok())
+            .unwrap_or(RiotId {
+                name: None,
+                tagline: None,
+            });
+
+        Player {
+            // Bots have no leagues to look up
+            puuid: participant.puuid.clone().filter(|_| !participant.bot),
+            riot_id,
+            team: Team(participant.team_id as usize),
+            champion: Champion::from(participant.champion_id),
+            summoner_spells: SummonerSpells([
+                SummonerSpell::new(participant.spell1_id as u32),
+                SummonerSpell::new(participant.spell2_id as u32),
+            ]),
+            // The keystone is always the first perk
+            keystone: participant
+                .perks
+                .as_ref()
+                .and_then(|perks| perks.perk_ids.first())
+                .map(|&id| Rune(id as usize)),
+        }
+    }
+}
@@ -1,8 +1,13 @@
+pub mod history;
 pub mod league;
+pub mod ranks;
+pub use history::History;
 pub use league::{Division, League, Tier};
+pub use ranks::Ranks;
 
 use crate::assets;
-use crate::{Account, Game};
+use crate::codec::{self, DecodeError, Payload};
+use crate::{Account, Client, Game, Region, RequestError, game};
 
 #[derive(Debug, Clone, bitcode::Encode, bitcode::Decode)]
 pub struct Data {
@@ -22,7 +27,9 @@ impl Data {
         let dummy = Account::dummy(account::RiotId::new(name, tagline));
 
         Self {
-            games: vec![Game::dummy(dummy.riot_id.clone()); 10],
+            games: std::iter::once(Game::dummy_arena(dummy.riot_id.clone()))
+                .chain(std::iter::repeat_n(Game::dummy(dummy.riot_id.clone()), 9))
+                .collect(),
             icon: assets::Image::from(bytes),
             summoner: Summoner {
                 account: dummy,
@@ -38,13 +45,63 @@ impl Data {
     }
 
     pub fn encode(&self) -> Vec<u8> {
-        let bytes = bitcode::encode(self);
-        lz4_flex::compress_prepend_size(&bytes)
+        codec::encode(self)
     }
 
-    pub fn decode(bytes: &[u8]) -> Self {
-        let decompressed = lz4_flex::decompress_size_prepended(bytes).unwrap();
-        bitcode::decode(&decompressed).unwrap()
+    pub fn decode(bytes: &[u8]) -> Result<Self, DecodeError> {
+        codec::decode(bytes)
+    }
+}
+
+impl Payload for Data {
+    const KIND: codec::Kind = codec::Kind::Summoner;
+    const VERSION: u16 = 5;
+
+    fn migrate(version: u16, body: &[u8]) -> Result<Self, DecodeError> {
+        let mut data = match version {
+            1 => codec::decode_body::<previous::Data<game::v1::Game>>(body).map(Self::from)?,
+            2 => codec::decode_body::<previous::Data<game::v2::Game>>(body).map(Self::from)?,
+            3 => codec::decode_body::<previous::Data<game::v3::Game>>(body).map(Self::from)?,
+            // Version 4 has the current layout, but cached queues named since by id
+            4 => codec::decode_body::<Self>(body)?,
+            _ => {
+                return Err(DecodeError::UnsupportedVersion {
+                    kind: Self::KIND,
+                    version,
+                });
+            }
+        };
+
+        for game in &mut data.games {
+            game.queue = game.queue.remap();
+        }
+
+        Ok(data)
+    }
+}
+
+mod previous {
+    use super::{League, Summoner};
+    use crate::{Game, assets};
+
+    /// The layout of [`super::Data`], with games in an older layout.
+    #[derive(bitcode::Decode)]
+    pub struct Data<G> {
+        pub icon: assets::Image,
+        pub summoner: Summoner,
+        pub leagues: Vec<League>,
+        pub games: Vec<G>,
+    }
+
+    impl<G: Into<Game>> From<Data<G>> for super::Data {
+        fn from(data: Data<G>) -> Self {
+            Self {
+                icon: data.icon,
+                summoner: data.summoner,
+                leagues: data.leagues,
+                games: data.games.into_iter().map(G::into).collect(),
+            }
+        }
     }
 }
 
@@ -57,6 +114,27 @@ pub struct Summoner {
 }
 
 impl Summoner {
+    pub async fn fetch(
+        client: &Client,
+        region: Region,
+        account: Account,
+    ) -> Result<Self, RequestError> {
+        let summoner = client
+            .as_ref()
+            .summoner_v4()
+            .get_by_puuid(region.into(), account.puuid.as_ref())
+            .await
+            .map_err(RequestError::internal)?
+            .ok_or(RequestError::NotFound)?;
+
+        Ok(Self {
+            account,
+            level: summoner.summoner_level,
+            icon_id: summoner.profile_icon_id,
+            last_modified: summoner.revision_date,
+        })
+    }
+
     pub fn puuid(&self) -> &str {
         self.account.puuid.as_ref()
     }
@@ -0,0 +1,254 @@
+//! Snapshots of the leagues of a summoner, used to infer the points won or lost per game.
+use crate::codec::{self, DecodeError, Payload};
+use crate::game;
+use crate::summoner::league::{self, League, Tier};
+
+use std::collections::HashMap;
+
+/// A league as it was first seen at `time`.
+#[derive(Debug, Clone, bitcode::Encode, bitcode::Decode)]
+pub struct Snapshot {
+    /// The time of the snapshot, in milliseconds since the Unix epoch.
+    pub time: i64,
+    pub league: League,
+}
+
+#[derive(Debug, Clone, Default, bitcode::Encode, bitcode::Decode)]
+pub struct History(Vec<Snapshot>);
+
+/// The points won or lost in a ranked game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Delta {
+    /// The [`Tier::distance`] between the ranks, so it spans promotions and demotions.
+    pub points: i32,
+    pub movement: Option<Movement>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Movement {
+    Promoted,
+    Demoted,
+}
+
+impl History {
+    /// Records the `leagues` seen at `time`, skipping the ones unchanged since their last
+    /// snapshot.
+    pub fn record(&mut self, time: i64, leagues: &[League]) {
+        for league in leagues {
+            if self.latest(&league.kind) == Some(league) {
+                continue;
+            }
+
+            self.0.push(Snapshot {
+                time,
+                league: league.clone(),
+            });
+        }
+
+        self.0.sort_by_key(|snapshot| snapshot.time);
+    }
+
+    /// Records the snapshots of `other`, taken after the known ones.
+    pub fn merge(&mut self, other: History) {
+        for snapshot in other.0 {
+            self.record(snapshot.time, std::slice::from_ref(&snapshot.league));
+        }
+    }
+
+    /// The snapshots of the league of `kind`, from the oldest.
+    pub fn snapshots(&self, kind: &league::Kind) -> impl Iterator<Item = &Snapshot> {
+        self.0
+            .iter()
+            .filter(move |snapshot| &snapshot.league.kind == kind)
+    }
+
+    fn latest(&self, kind: &league::Kind) -> Option<&League> {
+        self.snapshots(kind).last().map(|snapshot| &snapshot.league)
+    }
+
+    /// Infers the points won or lost in each ranked game of `games`.
+    ///
+    /// A game gets a delta when it is the only game of its queue played between two
+    /// consecutive snapshots, and the league counts exactly one more game.
+    pub fn deltas(&self, games: &game::Map) -> HashMap<game::Id, Delta> {
+        let mut deltas = HashMap::new();
+
+        for (queue, kind) in [
+            (game::Queue::RankedSolo, league::SummonersRift::Solo),
+            (game::Queue::RankedFlex, league::SummonersRift::Flex),
+        ] {
+            let kind = league::Kind::SummonersRift(kind);
+            let snapshots = self.snapshots(&kind).collect::<Vec<_>>();
+
+            for pair in snapshots.windows(2) {
+                let (before, after) = (pair[0], pair[1]);
+                let played = (after.league.wins + after.league.losses)
+                    .checked_sub(before.league.wins + before.league.losses);
+
+                let mut between = games.iter().filter(|(_, game)| {
+                    let ended_at = game.created_at + game.duration * 1000;
+
+                    game.queue == queue
+                        && (before.time..after.time).contains(&ended_at)
+                        && game
+                            .players
+                            .iter()
+                            .all(|player| player.result != game::Result::Remake)
+                });
+
+                let (Some((id, _)), None, Some(1)) = (between.next(), between.next(), played)
+                else {
+                    continue;
+                };
+
+                if let (Some(before), Some(after)) = (&before.league.tier, &after.league.tier) {
+                    deltas.insert(id.clone(), Delta::between(before, after));
+                }
+            }
+        }
+
+        deltas
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        codec::encode(self)
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self, DecodeError> {
+        codec::decode(bytes)
+    }
+}
+
+impl Payload for History {
+    const KIND: codec::Kind = codec::Kind::LeagueHistory;
+    const VERSION: u16 = 1;
+}
+
+impl Delta {
+    fn between(before: &Tier, after: &Tier) -> Self {
+        let movement = if after.is_above(before) {
+            Some(Movement::Promoted)
+        } else if before.is_above(after) {
+            Some(Movement::Demoted)
+        } else {
+            None
+        };
+
+        Self {
+            points: before.distance(after),
+            movement,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::summoner::Division;
+
+    const MINUTE: i64 = 60 * 1000;
+
+    fn solo(tier: Tier, wins: u32, losses: u32) -> League {
+        League {
+            kind: league::Kind::SummonersRift(league::SummonersRift::Solo),
+            tier: Some(tier),
+            wins,
+            losses,
+        }
+    }
+
+    /// Ranked solo games, each created at the given minute and lasting 30 minutes.
+    fn games(minutes: impl IntoIterator<Item = i64>) -> game::Map {
+        minutes
+            .into_iter()
+            .map(|minute| {
+                let id = id(minute);
+                let game = game::Game {
+                    id: id.clone(),
+                    queue: game::Queue::RankedSolo,
+                    created_at: minute * MINUTE,
+                    duration: 30 * 60,
+                    players: Vec::new(),
+                    teams: Vec::new(),
+                };
+
+                (id, game)
+            })
+            .collect()
+    }
+
+    fn id(minute: i64) -> game::Id {
+        game::Id::try_from(format!("BR1_{minute}")).unwrap()
+    }
+
+    #[test]
+    fn promotions_across_divisions() {
+        let mut history = History::default();
+        history.record(0, &[solo(Tier::Gold(Division::Four(90)), 10, 10)]);
+        history.record(
+            60 * MINUTE,
+            &[solo(Tier::Gold(Division::Three(10)), 11, 10)],
+        );
+
+        let deltas = history.deltas(&games([10]));
+
+        assert_eq!(
+            deltas.get(&id(10)),
+            Some(&Delta {
+                points: 20,
+                movement: Some(Movement::Promoted),
+            })
+        );
+    }
+
+    #[test]
+    fn promotions_and_demotions_across_tiers() {
+        let mut history = History::default();
+        history.record(0, &[solo(Tier::Gold(Division::One(95)), 10, 10)]);
+        history.record(
+            60 * MINUTE,
+            &[solo(Tier::Platinum(Division::Four(15)), 11, 10)],
+        );
+        history.record(120 * MINUTE, &[solo(Tier::Gold(Division::One(80)), 11, 11)]);
+
+        let deltas = history.deltas(&games([10, 70]));
+
+        assert_eq!(
+            deltas.get(&id(10)),
+            Some(&Delta {
+                points: 20,
+                movement: Some(Movement::Promoted),
+            })
+        );
+        assert_eq!(
+            deltas.get(&id(70)),
+            Some(&Delta {
+                points: -35,
+                movement: Some(Movement::Demoted),
+            })
+        );
+    }
+
+    #[test]
+    fn games_without_a_snapshot_after_them_have_no_delta() {
+        let mut history = History::default();
+        history.record(0, &[solo(Tier::Gold(Division::Two(40)), 10, 10)]);
+        history.record(60 * MINUTE, &[solo(Tier::Gold(Division::Two(60)), 11, 10)]);
+
+        let deltas = history.deltas(&games([10, 70]));
+
+        assert_eq!(deltas.len(), 1);
+        assert!(deltas.contains_key(&id(10)));
+        assert!(!deltas.contains_key(&id(70)));
+    }
+
+    #[test]
+    fn games_sharing_a_missing_snapshot_have_no_delta() {
+        let mut history = History::default();
+        history.record(0, &[solo(Tier::Gold(Division::Two(40)), 10, 10)]);
+        // The snapshot between both games was never taken
+        history.record(120 * MINUTE, &[solo(Tier::Gold(Division::Two(20)), 11, 11)]);
+
+        assert!(history.deltas(&games([10, 50])).is_empty());
+    }
+}
@@ -1,5 +1,30 @@
+use crate::{Client, Region, RequestError, game};
 use riven::models::league_v4;
 
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+/// The index of Master, the first tier without divisions.
+const APEX: u8 = 7;
+
+/// The ladder positions of a division, from 0 to 100 LP.
+const DIVISION_SPAN: u32 = 101;
+
+/// The names of the tiers, from Iron.
+const NAMES: [&str; 10] = [
+    "Iron",
+    "Bronze",
+    "Silver",
+    "Gold",
+    "Platinum",
+    "Emerald",
+    "Diamond",
+    "Master",
+    "Grandmaster",
+    "Challenger",
+];
+
 #[derive(Debug, Clone, PartialEq, Eq, bitcode::Encode, bitcode::Decode)]
 pub enum Kind {
     SummonersRift(SummonersRift),
@@ -29,6 +54,16 @@ impl From<riven::consts::QueueType> for Kind {
     }
 }
 
+impl Kind {
+    /// The league ranking the players of `queue`, solo queue outside of ranked queues.
+    pub fn ranking(queue: game::Queue) -> Self {
+        Kind::SummonersRift(match queue {
+            game::Queue::RankedFlex => SummonersRift::Flex,
+            _ => SummonersRift::Solo,
+        })
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, bitcode::Encode, bitcode::Decode)]
 pub enum SummonersRift {
     Solo,
@@ -43,7 +78,7 @@ pub enum TeamfightTactics {
     DoubleUp,
 }
 
-#[derive(Debug, Clone, bitcode::Encode, bitcode::Decode)]
+#[derive(Debug, Clone, PartialEq, Eq, bitcode::Encode, bitcode::Decode)]
 pub struct League {
     pub kind: Kind,
     pub tier: Option<Tier>,
@@ -52,6 +87,21 @@ pub struct League {
 }
 
 impl League {
+    pub async fn fetch(
+        client: &Client,
+        region: Region,
+        puuid: &str,
+    ) -> Result<Vec<Self>, RequestError> {
+        let entries = client
+            .as_ref()
+            .league_v4()
+            .get_league_entries_by_puuid(region.into(), puuid)
+            .await
+            .map_err(RequestError::internal)?;
+
+        Ok(entries.into_iter().map(League::from).collect())
+    }
+
     pub fn division(&self) -> Option<Division> {
         self.tier.as_ref().and_then(Tier::division)
     }
@@ -87,7 +137,7 @@ impl From<league_v4::LeagueEntry> for League {
     }
 }
 
-#[derive(Debug, Copy, Clone, bitcode::Encode, bitcode::Decode)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, bitcode::Encode, bitcode::Decode)]
 pub enum Division {
     One(u8),
     Two(u8),
@@ -95,7 +145,55 @@ pub enum Division {
     Four(u8),
 }
 
-#[derive(Debug, Copy, Clone, bitcode::Encode, bitcode::Decode)]
+impl Division {
+    pub fn points(&self) -> u8 {
+        match self {
+            Division::One(points)
+            | Division::Two(points)
+            | Division::Three(points)
+            | Division::Four(points) => *points,
+        }
+    }
+
+    /// The number of the division, from 1 at the top of a tier to 4.
+    pub fn number(&self) -> u8 {
+        4 - self.step()
+    }
+
+    /// The index of the division from Four.
+    fn step(&self) -> u8 {
+        match self {
+            Division::Four(_) => 0,
+            Division::Three(_) => 1,
+            Division::Two(_) => 2,
+            Division::One(_) => 3,
+        }
+    }
+
+    fn from_step(step: u8, points: u8) -> Self {
+        match step {
+            0 => Division::Four(points),
+            1 => Division::Three(points),
+            2 => Division::Two(points),
+            _ => Division::One(points),
+        }
+    }
+}
+
+/// Divisions are ordered from Four to One, then by points.
+impl Ord for Division {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.step(), self.points()).cmp(&(other.step(), other.points()))
+    }
+}
+
+impl PartialOrd for Division {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, bitcode::Encode, bitcode::Decode)]
 pub enum Tier {
     Iron(Division),
     Bronze(Division),
@@ -110,6 +208,9 @@ pub enum Tier {
 }
 
 impl Tier {
+    /// The ladder positions spanned by a tier with divisions.
+    pub const SPAN: u32 = 4 * DIVISION_SPAN;
+
     pub fn division(&self) -> Option<Division> {
         match self {
             Tier::Challenger(_) | Tier::Grandmaster(_) | Tier::Master(_) => None,
@@ -132,14 +233,222 @@ impl Tier {
             | Tier::Gold(division)
             | Tier::Platinum(division)
             | Tier::Emerald(division)
-            | Tier::Diamond(division) => match division {
-                Division::One(points)
-                | Division::Two(points)
-                | Division::Three(points)
-                | Division::Four(points) => *points as u16,
-            },
+            | Tier::Diamond(division) => division.points() as u16,
         }
     }
+
+    /// The name of the tier, without its division.
+    pub fn name(&self) -> &'static str {
+        NAMES[self.rank().0 as usize]
+    }
+
+    /// The position on the whole ladder, above Iron 4 with 0 LP.
+    ///
+    /// Each division spans 0 to 100 LP, so 100 LP is right below the next division with 0 LP.
+    /// Master and above share a single ladder, starting right above Diamond 1.
+    pub fn position(&self) -> u32 {
+        let points = self.points() as u32;
+
+        match self.rank() {
+            // Apex tiers only differ by their points
+            (tier, _) if tier >= APEX => APEX as u32 * Self::SPAN + points,
+            (tier, step) => tier as u32 * Self::SPAN + step as u32 * DIVISION_SPAN + points,
+        }
+    }
+
+    /// The rank at `position` on the ladder, the inverse of [`Tier::position`].
+    ///
+    /// Positions past Diamond 1 are all Master, as the apex tiers cannot be told apart by
+    /// their points alone.
+    pub fn from_position(position: u32) -> Self {
+        let apex = APEX as u32 * Self::SPAN;
+
+        if position >= apex {
+            return Tier::Master((position - apex).min(u16::MAX as u32) as u16);
+        }
+
+        let tier = (position / Self::SPAN) as u8;
+        let step = (position % Self::SPAN / DIVISION_SPAN) as u8;
+
+        Tier::from_rank(tier, step, (position % DIVISION_SPAN) as u16)
+    }
+
+    /// The rank at the average ladder position of the `tiers`, if any.
+    ///
+    /// Like [`Tier::from_position`], an average above Diamond 1 is always Master.
+    pub fn average(tiers: impl IntoIterator<Item = Tier>) -> Option<Tier> {
+        let (count, total) = tiers
+            .into_iter()
+            .fold((0u32, 0u64), |(count, total), tier| {
+                (count + 1, total + tier.position() as u64)
+            });
+
+        (count > 0).then(|| Tier::from_position((total / count as u64) as u32))
+    }
+
+    /// The league points to climb from `self` to `other`, negative when `other` is lower.
+    ///
+    /// Unlike positions, 100 LP in a division is as high as the next division with 0 LP.
+    pub fn distance(&self, other: &Tier) -> i32 {
+        let points = |tier: &Tier| {
+            let points = tier.points() as i32;
+
+            match tier.rank() {
+                (index, _) if index >= APEX => APEX as i32 * 400 + points,
+                (index, step) => index as i32 * 400 + step as i32 * 100 + points,
+            }
+        };
+
+        points(other) - points(self)
+    }
+
+    /// The division or tier right above, with 0 LP.
+    pub fn next(&self) -> Option<Tier> {
+        match self.rank() {
+            (9, _) => None,
+            // Diamond 1 promotes to Master, and apex tiers have no divisions
+            (tier, 3) | (tier @ APEX.., _) => Some(Tier::from_rank(tier + 1, 0, 0)),
+            (tier, step) => Some(Tier::from_rank(tier, step + 1, 0)),
+        }
+    }
+
+    /// The division or tier right below, with 0 LP.
+    pub fn previous(&self) -> Option<Tier> {
+        match self.rank() {
+            (0, 0) => None,
+            // Master demotes to Diamond 1
+            (tier, 0) | (tier @ APEX.., _) => Some(Tier::from_rank(tier - 1, 3, 0)),
+            (tier, step) => Some(Tier::from_rank(tier, step - 1, 0)),
+        }
+    }
+
+    /// Whether `self` is a higher tier or division than `other`, regardless of points.
+    pub fn is_above(&self, other: &Tier) -> bool {
+        self.rank() > other.rank()
+    }
+
+    /// The index of the tier from Iron, and of the division from Four.
+    fn rank(&self) -> (u8, u8) {
+        match self {
+            Tier::Iron(division) => (0, division.step()),
+            Tier::Bronze(division) => (1, division.step()),
+            Tier::Silver(division) => (2, division.step()),
+            Tier::Gold(division) => (3, division.step()),
+            Tier::Platinum(division) => (4, division.step()),
+            Tier::Emerald(division) => (5, division.step()),
+            Tier::Diamond(division) => (6, division.step()),
+            Tier::Master(_) => (7, 0),
+            Tier::Grandmaster(_) => (8, 0),
+            Tier::Challenger(_) => (9, 0),
+        }
+    }
+
+    /// The inverse of [`Tier::rank`], the `step` is ignored for apex tiers.
+    fn from_rank(tier: u8, step: u8, points: u16) -> Self {
+        let division = Division::from_step(step, points.min(u8::MAX as u16) as u8);
+
+        match tier {
+            0 => Tier::Iron(division),
+            1 => Tier::Bronze(division),
+            2 => Tier::Silver(division),
+            3 => Tier::Gold(division),
+            4 => Tier::Platinum(division),
+            5 => Tier::Emerald(division),
+            6 => Tier::Diamond(division),
+            7 => Tier::Master(points),
+            8 => Tier::Grandmaster(points),
+            _ => Tier::Challenger(points),
+        }
+    }
+}
+
+/// Tiers are ordered by tier and division, then by points.
+///
+/// Unlike [`Tier::position`], a Grandmaster is always above a Master, whatever their points.
+impl Ord for Tier {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.rank(), self.points()).cmp(&(other.rank(), other.points()))
+    }
+}
+
+impl PartialOrd for Tier {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Formats the tier as `Platinum 2 50 LP`, or `Master 120 LP` for apex tiers.
+impl fmt::Display for Tier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.division() {
+            Some(division) => write!(
+                f,
+                "{} {} {} LP",
+                self.name(),
+                division.number(),
+                self.points()
+            ),
+            None => write!(f, "{} {} LP", self.name(), self.points()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ParseError {
+    #[error("no tier was given")]
+    Empty,
+    #[error("unknown tier: {0}")]
+    Tier(String),
+    #[error("invalid or missing division")]
+    Division,
+    #[error("invalid league points")]
+    Points,
+}
+
+/// Parses the format of [`Tier`]'s `Display`, ignoring case.
+///
+/// Divisions may also be roman numerals, and the points default to 0 when omitted.
+impl FromStr for Tier {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut words = value.split_whitespace();
+
+        let name = words.next().ok_or(ParseError::Empty)?;
+        let tier = NAMES
+            .iter()
+            .position(|tier| tier.eq_ignore_ascii_case(name))
+            .ok_or_else(|| ParseError::Tier(name.to_string()))? as u8;
+
+        let step = if tier < APEX {
+            let division = words.next().ok_or(ParseError::Division)?;
+
+            match division.to_ascii_uppercase().as_str() {
+                "1" | "I" => 3,
+                "2" | "II" => 2,
+                "3" | "III" => 1,
+                "4" | "IV" => 0,
+                _ => return Err(ParseError::Division),
+            }
+        } else {
+            0
+        };
+
+        let points = match (words.next(), words.next(), words.next()) {
+            (None, _, _) => 0,
+            (Some(points), Some(unit), None) if unit.eq_ignore_ascii_case("LP") => {
+                points.parse::<u16>().map_err(|_| ParseError::Points)?
+            }
+            _ => return Err(ParseError::Points),
+        };
+
+        // Points of a division never go past 100, the promotion threshold
+        if tier < APEX && points > 100 {
+            return Err(ParseError::Points);
+        }
+
+        Ok(Tier::from_rank(tier, step, points))
+    }
 }
 
 impl TryFrom<league_v4::LeagueEntry> for Tier {
@@ -178,3 +487,142 @@ impl TryFrom<league_v4::LeagueEntry> for Tier {
             .ok_or(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIVISIONS: [fn(u8) -> Division; 4] = [
+        Division::Four,
+        Division::Three,
+        Division::Two,
+        Division::One,
+    ];
+
+    const TIERS: [fn(Division) -> Tier; 7] = [
+        Tier::Iron,
+        Tier::Bronze,
+        Tier::Silver,
+        Tier::Gold,
+        Tier::Platinum,
+        Tier::Emerald,
+        Tier::Diamond,
+    ];
+
+    #[test]
+    fn ordering() {
+        assert!(Tier::Gold(Division::Four(0)) > Tier::Silver(Division::One(100)));
+        assert!(Tier::Gold(Division::Three(0)) > Tier::Gold(Division::Four(99)));
+        assert!(Tier::Gold(Division::Four(50)) > Tier::Gold(Division::Four(49)));
+        assert!(Tier::Master(0) > Tier::Diamond(Division::One(100)));
+        assert!(Tier::Grandmaster(0) > Tier::Master(500));
+        assert!(Tier::Challenger(0) > Tier::Grandmaster(900));
+        assert!(Tier::Challenger(1200) > Tier::Challenger(1100));
+    }
+
+    #[test]
+    fn position_round_trip() {
+        for tier in TIERS {
+            for division in DIVISIONS {
+                for points in [0, 1, 50, 99, 100] {
+                    let rank = tier(division(points));
+
+                    assert_eq!(Tier::from_position(rank.position()), rank);
+                }
+            }
+        }
+
+        for points in [0, 120, 1500] {
+            assert_eq!(
+                Tier::from_position(Tier::Master(points).position()),
+                Tier::Master(points)
+            );
+        }
+    }
+
+    #[test]
+    fn positions_follow_ordering() {
+        let positions = TIERS
+            .iter()
+            .flat_map(|tier| {
+                DIVISIONS
+                    .iter()
+                    .flat_map(move |division| [0, 100].map(|points| tier(division(points))))
+            })
+            .chain([Tier::Master(0)])
+            .map(|tier| tier.position())
+            .collect::<Vec<_>>();
+
+        assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn distance() {
+        let gold_two = Tier::Gold(Division::Two(90));
+
+        assert_eq!(gold_two.distance(&Tier::Gold(Division::One(10))), 20);
+        assert_eq!(gold_two.distance(&Tier::Gold(Division::Two(70))), -20);
+        assert_eq!(
+            Tier::Diamond(Division::One(80)).distance(&Tier::Master(15)),
+            35
+        );
+    }
+
+    #[test]
+    fn next_and_previous() {
+        assert_eq!(
+            Tier::Iron(Division::Four(40)).next(),
+            Some(Tier::Iron(Division::Three(0)))
+        );
+        assert_eq!(Tier::Iron(Division::Four(40)).previous(), None);
+
+        assert_eq!(
+            Tier::Diamond(Division::One(75)).next(),
+            Some(Tier::Master(0))
+        );
+        assert_eq!(
+            Tier::Diamond(Division::One(75)).previous(),
+            Some(Tier::Diamond(Division::Two(0)))
+        );
+
+        assert_eq!(Tier::Master(300).next(), Some(Tier::Grandmaster(0)));
+        assert_eq!(
+            Tier::Master(300).previous(),
+            Some(Tier::Diamond(Division::One(0)))
+        );
+
+        assert_eq!(Tier::Challenger(1500).next(), None);
+        assert_eq!(
+            Tier::Challenger(1500).previous(),
+            Some(Tier::Grandmaster(0))
+        );
+    }
+
+    #[test]
+    fn parse_and_format() {
+        let platinum: Tier = "Platinum 2 50 LP".parse().unwrap();
+        assert_eq!(platinum, Tier::Platinum(Division::Two(50)));
+        assert_eq!(platinum.to_string(), "Platinum 2 50 LP");
+
+        let gold: Tier = "gold iv".parse().unwrap();
+        assert_eq!(gold, Tier::Gold(Division::Four(0)));
+        assert_eq!(gold.to_string(), "Gold 4 0 LP");
+        assert_eq!(gold.to_string().parse::<Tier>(), Ok(gold));
+
+        let master: Tier = "Master 120 LP".parse().unwrap();
+        assert_eq!(master, Tier::Master(120));
+        assert_eq!(master.to_string(), "Master 120 LP");
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!("".parse::<Tier>(), Err(ParseError::Empty));
+        assert_eq!(
+            "Wood 4".parse::<Tier>(),
+            Err(ParseError::Tier(String::from("Wood")))
+        );
+        assert_eq!("Gold 5".parse::<Tier>(), Err(ParseError::Division));
+        assert_eq!("Gold 2 150 LP".parse::<Tier>(), Err(ParseError::Points));
+        assert_eq!("Gold 2 50".parse::<Tier>(), Err(ParseError::Points));
+    }
+}
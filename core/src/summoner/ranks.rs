@@ -0,0 +1,145 @@
+//! The leagues of the players met in games, used to rank their lobbies.
+use crate::codec::{self, DecodeError, Payload};
+use crate::game::Queue;
+use crate::summoner::league::{self, League, Tier};
+use crate::{Client, Game, Region};
+
+use std::collections::BTreeMap;
+
+/// How long fetched leagues stay fresh, in milliseconds.
+const LIFETIME: i64 = 24 * 60 * 60 * 1000;
+
+/// The most puuids requested at once.
+pub const BATCH: usize = 50;
+
+/// The puuid of bots in match data.
+const BOT: &str = "BOT";
+
+/// The leagues of many summoners, by puuid.
+#[derive(Debug, Clone, Default, bitcode::Encode, bitcode::Decode)]
+pub struct Ranks(BTreeMap<String, Entry>);
+
+#[derive(Debug, Clone, bitcode::Encode, bitcode::Decode)]
+struct Entry {
+    /// When the leagues were fetched, in milliseconds since the Unix epoch.
+    time: i64,
+    leagues: Vec<League>,
+}
+
+impl Ranks {
+    /// Fetches the leagues of every puuid concurrently, skipping bots.
+    ///
+    /// The summoners whose leagues failed to be fetched are left out, so they stay missing.
+    pub async fn fetch(client: &Client, region: Region, puuids: &[String]) -> Self {
+        let time = now();
+        let requests = puuids
+            .iter()
+            .filter(|puuid| is_player(puuid))
+            .map(|puuid| async move { (puuid, League::fetch(client, region, puuid).await) });
+
+        let entries = futures::future::join_all(requests)
+            .await
+            .into_iter()
+            .filter_map(|(puuid, leagues)| match leagues {
+                Ok(leagues) => Some((puuid.clone(), Entry { time, leagues })),
+                Err(error) => {
+                    tracing::warn!("Failed to fetch the leagues of `{puuid}`: {error}");
+                    None
+                }
+            })
+            .collect();
+
+        Self(entries)
+    }
+
+    #[cfg(feature = "dummy")]
+    pub fn dummy(puuids: &[String]) -> Self {
+        let time = now();
+
+        Self(
+            puuids
+                .iter()
+                .map(|puuid| {
+                    let leagues = vec![
+                        League::dummy(league::Kind::SummonersRift(league::SummonersRift::Solo)),
+                        League::dummy(league::Kind::SummonersRift(league::SummonersRift::Flex)),
+                    ];
+
+                    (puuid.clone(), Entry { time, leagues })
+                })
+                .collect(),
+        )
+    }
+
+    /// The `puuids` whose leagues are unknown or stale, skipping bots.
+    pub fn missing<'a>(&self, puuids: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+        let stale = now() - LIFETIME;
+        let mut missing: Vec<String> = puuids
+            .into_iter()
+            .filter(|puuid| is_player(puuid))
+            .filter(|puuid| self.0.get(*puuid).is_none_or(|entry| entry.time < stale))
+            .map(String::from)
+            .collect();
+
+        missing.sort_unstable();
+        missing.dedup();
+
+        missing
+    }
+
+    /// Merges the `other` leagues, replacing the ones known of the same summoners.
+    ///
+    /// Stale leagues are kept until refetched, so lobbies stay ranked meanwhile.
+    pub fn merge(&mut self, other: Ranks) {
+        self.0.extend(other.0);
+    }
+
+    pub fn leagues(&self, puuid: &str) -> &[League] {
+        self.0
+            .get(puuid)
+            .map(|entry| entry.leagues.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// The league of `puuid` ranking the `queue`, see [`league::Kind::ranking`].
+    pub fn league(&self, puuid: &str, queue: Queue) -> Option<&League> {
+        let kind = league::Kind::ranking(queue);
+
+        self.leagues(puuid)
+            .iter()
+            .find(|league| league.kind == kind)
+    }
+
+    /// The average rank of the players of `game`, in the league of its queue.
+    ///
+    /// Games outside of ranked queues are ranked by the solo queue league of their players.
+    pub fn average(&self, game: &Game) -> Option<Tier> {
+        Tier::average(
+            game.players
+                .iter()
+                .filter_map(|player| self.league(&player.puuid, game.queue)?.tier),
+        )
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        codec::encode(self)
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self, DecodeError> {
+        codec::decode(bytes)
+    }
+}
+
+impl Payload for Ranks {
+    const KIND: codec::Kind = codec::Kind::Ranks;
+    const VERSION: u16 = 1;
+}
+
+/// Whether the `puuid` belongs to a player, bots have none or a placeholder.
+fn is_player(puuid: &str) -> bool {
+    !puuid.is_empty() && puuid != BOT
+}
+
+fn now() -> i64 {
+    (time::OffsetDateTime::now_utc().unix_timestamp_nanos() / 1_000_000) as i64
+}
@@ -2,21 +2,46 @@ use aery_core::summoner;
 use iced::Task;
 use iced::widget::image::Handle;
 
-use std::collections::HashMap;
-use std::sync::LazyLock;
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{Arc, LazyLock};
 
-use crate::core::assets::emblem;
+use crate::core::assets::{Icon, Image, Index, champion, emblem, index, item, rune, spell};
 use crate::{Message, core};
 
 type HandleMap<T> = HashMap<T, Handle>;
+type DataMap<T, D> = HashMap<T, D>;
+
+/// Where the icons are loaded from, once they are first looked up.
+#[derive(Debug, Clone)]
+enum Source {
+    /// A blob of icons kept in memory.
+    Memory(Arc<[u8]>),
+    /// The icons of the cached assets.
+    Cache,
+    /// The backend, one icon at a time.
+    Backend,
+}
 
 #[derive(Debug, Clone)]
 pub struct Assets {
-    champion: HandleMap<core::Champion>,
-    rune: HandleMap<core::Rune>,
-    spell: HandleMap<core::SummonerSpell>,
-    item: HandleMap<core::Item>,
+    version: String,
+    source: Source,
+    locations: Arc<BTreeMap<Icon, index::Location>>,
+    icons: HandleMap<Icon>,
     emblem: HandleMap<emblem::Id>,
+    champion_data: DataMap<core::Champion, champion::Data>,
+    rune_data: DataMap<core::Rune, rune::Data>,
+    spell_data: DataMap<core::SummonerSpell, spell::Data>,
+    item_data: DataMap<core::Item, item::Data>,
+    /// Icons looked up before being loaded, see [`Assets::load_missing`].
+    missing: RefCell<HashSet<Icon>>,
+    requested: HashSet<Icon>,
+    /// Kind and id of every looked up entry that is not part of the assets.
+    unknown: RefCell<HashSet<(&'static str, u32)>>,
+    /// Whether an unknown entry was looked up since the last refresh check.
+    outdated: Cell<bool>,
+    refresh_requested: bool,
 }
 
 impl Assets {
@@ -24,80 +49,299 @@ impl Assets {
         Task::perform(Assets::new(), Message::AssetsLoaded)
     }
 
-    pub async fn new() -> Result<Assets, String> {
-        let bytes = fetch().await?;
-        let unloaded = core::Assets::decode(bytes);
+    /// Requests the latest assets version, so outdated assets can be refreshed in the background.
+    pub fn check_version() -> Task<Message> {
+        Task::perform(
+            crate::backend().assets_version(),
+            Message::AssetsVersionFetched,
+        )
+    }
+
+    pub fn refresh(version: String) -> Task<Message> {
+        Task::perform(Assets::update(version), Message::AssetsRefreshed)
+    }
+
+    pub async fn new() -> Result<Assets, core::RequestError> {
+        if let Some(index) = crate::cache().and_then(core::cache::Store::assets_index) {
+            tracing::info!("Using cached assets ({})", index.version);
+            return Ok(Assets::from_index(index, Source::Cache));
+        }
+
+        Assets::fetch(String::from("latest")).await
+    }
+
+    /// Updates the cached assets to `version` by applying a delta, falling back to fetching them
+    /// in full if there is no delta for the cached version.
+    async fn update(version: String) -> Result<Assets, core::RequestError> {
+        let Some(cache) = crate::cache() else {
+            return Assets::fetch(version).await;
+        };
+
+        let Some(mut cached) = cache.assets() else {
+            return Assets::fetch(version).await;
+        };
+
+        match crate::backend().assets_delta(cached.version.clone()).await {
+            Ok(delta) => {
+                let from = cached.version.clone();
+
+                match cached.apply(delta) {
+                    Ok(()) if cached.version == version => {
+                        tracing::info!("Updated assets from {from} to {version}");
+
+                        return Ok(Assets::save(cached));
+                    }
+                    Ok(()) => tracing::warn!(
+                        "Assets delta led to version {}, expected {version}",
+                        cached.version
+                    ),
+                    Err(error) => tracing::warn!("Failed to apply assets delta: {error}"),
+                }
+            }
+            Err(error) => tracing::info!("No assets delta from {}: {error}", cached.version),
+        }
+
+        Assets::fetch(version).await
+    }
+
+    /// Fetches the assets `version`, in full if they can be cached or only their index otherwise.
+    async fn fetch(version: String) -> Result<Assets, core::RequestError> {
+        if crate::cache().is_none() {
+            let index = crate::backend().assets_index(version).await?;
+
+            return Ok(Assets::from_index(index, Source::Backend));
+        }
+
+        let unloaded = crate::backend().assets(version).await?;
+
+        Ok(Assets::save(unloaded))
+    }
+
+    /// Caches the assets, keeping their icons in memory if that fails.
+    fn save(unloaded: core::Assets) -> Assets {
+        let (index, icons) = unloaded.into_index();
+
+        let Some(cache) = crate::cache() else {
+            return Assets::from_index(index, Source::Memory(icons.into()));
+        };
+
+        if let Err(error) = cache.save_assets(&index, &icons) {
+            tracing::warn!("Failed to cache assets: {error}");
+            return Assets::from_index(index, Source::Memory(icons.into()));
+        }
+
+        Assets::from_index(index, Source::Cache)
+    }
 
-        let champion = unloaded
+    fn from_index(index: Index, source: Source) -> Self {
+        let champion_data = index
             .champion
-            .0
             .into_iter()
-            .map(|(id, asset)| {
-                (
-                    core::Champion::new(id.0 as u32),
-                    Handle::from_bytes(asset.icon),
-                )
-            })
+            .map(|(id, data)| (core::Champion::new(id.0 as u32), data))
             .collect();
-        let rune = unloaded
+        let rune_data = index
             .rune
-            .0
             .into_iter()
-            .map(|(id, asset)| (core::Rune(id.0 as usize), Handle::from_bytes(asset.icon)))
+            .map(|(id, data)| (core::Rune(id.0 as usize), data))
             .collect();
-        let spell = unloaded
+        let spell_data = index
             .spell
-            .0
             .into_iter()
-            .map(|(id, asset)| {
-                (
-                    core::SummonerSpell::new(id.0),
-                    Handle::from_bytes(asset.icon),
-                )
-            })
+            .map(|(id, data)| (core::SummonerSpell::new(id.0), data))
             .collect();
-        let item = unloaded
+        let item_data = index
             .item
-            .0
             .into_iter()
-            .map(|(id, asset)| (core::Item(id.0 as usize), Handle::from_bytes(asset.icon)))
+            .map(|(id, data)| (core::Item(id.0 as usize), data))
             .collect();
-        let emblem = unloaded
+        let emblem = index
             .emblem
             .0
             .into_iter()
             .map(|(id, asset)| (id, Handle::from_bytes(asset.icon)))
             .collect();
 
-        Ok(Assets {
-            champion,
-            rune,
-            spell,
-            item,
+        Assets {
+            version: index.version,
+            source,
+            locations: Arc::new(index.icons),
+            icons: HandleMap::new(),
             emblem,
-        })
+            champion_data,
+            rune_data,
+            spell_data,
+            item_data,
+            missing: RefCell::default(),
+            requested: HashSet::new(),
+            unknown: RefCell::default(),
+            outdated: Cell::new(false),
+            refresh_requested: false,
+        }
+    }
+
+    /// Starts loading the icons that were looked up since the last call, rendered with
+    /// [`missing`] until [`Assets::insert_icons`] is called with them.
+    pub fn load_missing(&mut self) -> Task<Message> {
+        let icons = self
+            .missing
+            .take()
+            .into_iter()
+            .filter(|icon| self.requested.insert(*icon))
+            .collect::<Vec<_>>();
+
+        if icons.is_empty() {
+            return Task::none();
+        }
+
+        Task::perform(
+            load(
+                self.version.clone(),
+                self.source.clone(),
+                self.locations.clone(),
+                icons,
+            ),
+            Message::IconsLoaded,
+        )
+    }
+
+    /// Checks for newer assets once an entry missing from these ones is looked up, as it likely
+    /// comes from a newer patch.
+    pub fn check_unknown(&mut self) -> Task<Message> {
+        if !self.outdated.take() || self.refresh_requested {
+            return Task::none();
+        }
+
+        self.refresh_requested = true;
+
+        Assets::check_version()
+    }
+
+    /// Lets [`Assets::check_unknown`] check for newer assets again, once the last check is over.
+    pub fn finish_refresh(&mut self) {
+        self.refresh_requested = false;
+    }
+
+    pub fn insert_icons(&mut self, icons: Vec<(Icon, Handle)>) {
+        self.icons.extend(icons);
+    }
+
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Whether these assets are older than the given `version`.
+    pub fn is_outdated(&self, version: &str) -> bool {
+        core::assets::compare_versions(&self.version, version).is_lt()
     }
 
     pub fn champion(&self, id: &core::Champion) -> Handle {
-        self.champion.get(id).cloned().unwrap()
+        self.icon(Icon::Champion(champion::Id(id.id() as u16)))
     }
 
     pub fn rune(&self, id: &core::Rune) -> Handle {
-        self.rune.get(id).cloned().unwrap_or_else(|| missing())
+        self.icon(Icon::Rune(rune::Id(id.0 as u16)))
     }
 
     pub fn spell(&self, id: &core::SummonerSpell) -> Handle {
-        self.spell.get(id).cloned().unwrap()
+        self.icon(Icon::Spell(spell::Id(id.id())))
     }
 
     pub fn item(&self, id: &core::Item) -> Handle {
-        self.item.get(id).cloned().unwrap()
+        self.icon(Icon::Item(item::Id(id.0 as u32)))
     }
 
     pub fn emblem(&self, tier: &summoner::Tier) -> Handle {
         let id = emblem::Id::from_tier(tier);
-        self.emblem.get(&id).cloned().unwrap()
+
+        self.emblem.get(&id).cloned().unwrap_or_else(|| {
+            self.report_unknown("emblem", id.0 as u32);
+            missing()
+        })
+    }
+
+    pub fn champion_data(&self, id: &core::Champion) -> Option<&champion::Data> {
+        self.champion_data.get(id)
+    }
+
+    pub fn rune_data(&self, id: &core::Rune) -> Option<&rune::Data> {
+        self.rune_data.get(id)
+    }
+
+    pub fn spell_data(&self, id: &core::SummonerSpell) -> Option<&spell::Data> {
+        self.spell_data.get(id)
+    }
+
+    pub fn item_data(&self, id: &core::Item) -> Option<&item::Data> {
+        self.item_data.get(id)
     }
+
+    fn icon(&self, icon: Icon) -> Handle {
+        if let Some(handle) = self.icons.get(&icon) {
+            return handle.clone();
+        }
+
+        if self.locations.contains_key(&icon) {
+            self.missing.borrow_mut().insert(icon);
+        } else {
+            self.report_unknown(icon.kind(), icon.id());
+        }
+
+        missing()
+    }
+
+    fn report_unknown(&self, kind: &'static str, id: u32) {
+        if self.unknown.borrow_mut().insert((kind, id)) {
+            tracing::warn!("Unknown {kind} {id} in assets {}", self.version);
+            self.outdated.set(true);
+        }
+    }
+}
+
+async fn load(
+    version: String,
+    source: Source,
+    locations: Arc<BTreeMap<Icon, index::Location>>,
+    icons: Vec<Icon>,
+) -> Vec<(Icon, Handle)> {
+    let loaded = match source {
+        Source::Backend => crate::backend().icons(version, icons).await,
+        Source::Memory(blob) => icons
+            .into_iter()
+            .map(|icon| {
+                let image = locations
+                    .get(&icon)
+                    .and_then(|location| location.get(&blob))
+                    .map(|bytes| Image::from(bytes.to_vec()))
+                    .ok_or(core::RequestError::NotFound);
+
+                (icon, image)
+            })
+            .collect(),
+        Source::Cache => icons
+            .into_iter()
+            .map(|icon| {
+                let image = match (crate::cache(), locations.get(&icon)) {
+                    (Some(cache), Some(location)) => cache
+                        .icon(&version, *location)
+                        .map_err(core::RequestError::internal),
+                    _ => Err(core::RequestError::NotFound),
+                };
+
+                (icon, image)
+            })
+            .collect(),
+    };
+
+    loaded
+        .into_iter()
+        .filter_map(|(icon, image)| match image {
+            Ok(image) => Some((icon, Handle::from_bytes(image))),
+            Err(error) => {
+                tracing::warn!("Failed to load {} icon {}: {error}", icon.kind(), icon.id());
+                None
+            }
+        })
+        .collect()
 }
 
 pub fn missing() -> Handle {
@@ -110,25 +354,3 @@ pub fn missing() -> Handle {
 
     HANDLE.clone()
 }
-
-#[cfg(not(feature = "dummy"))]
-pub async fn fetch() -> Result<Vec<u8>, String> {
-    use futures::TryFutureExt;
-
-    let worker_url = dotenv_codegen::dotenv!("WORKER_URL");
-    let path = format!("{worker_url}/assets/latest");
-    tracing::info!("Requesting assets to {path}");
-
-    reqwest::get(path)
-        .map_err(|e| e.to_string())
-        .await?
-        .bytes()
-        .map_err(|e| e.to_string())
-        .await
-        .map(|b| b.to_vec())
-}
-
-#[cfg(feature = "dummy")]
-pub async fn fetch() -> Result<Vec<u8>, String> {
-    std::fs::read("assets/dummy/assets.aery").map_err(|error| error.to_string())
-}
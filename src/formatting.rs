@@ -3,19 +3,7 @@ use crate::core::game;
 use crate::core::summoner;
 
 pub fn tier(tier: summoner::Tier) -> String {
-    match tier {
-        summoner::Tier::Iron(_) => "Iron",
-        summoner::Tier::Bronze(_) => "Bronze",
-        summoner::Tier::Silver(_) => "Silver",
-        summoner::Tier::Gold(_) => "Gold",
-        summoner::Tier::Platinum(_) => "Platinum",
-        summoner::Tier::Emerald(_) => "Emerald",
-        summoner::Tier::Diamond(_) => "Diamond",
-        summoner::Tier::Master(_) => "Master",
-        summoner::Tier::Grandmaster(_) => "Grandmaster",
-        summoner::Tier::Challenger(_) => "Challenger",
-    }
-    .to_string()
+    tier.name().to_string()
 }
 
 pub fn division_or_points(tier: summoner::Tier) -> String {
@@ -34,13 +22,7 @@ pub fn division_or_points(tier: summoner::Tier) -> String {
 }
 
 pub fn division(division: summoner::Division) -> String {
-    match division {
-        summoner::Division::One(_) => "1",
-        summoner::Division::Two(_) => "2",
-        summoner::Division::Three(_) => "3",
-        summoner::Division::Four(_) => "4",
-    }
-    .to_string()
+    division.number().to_string()
 }
 
 pub fn duration(duration: time::Duration) -> String {
@@ -50,6 +32,13 @@ pub fn duration(duration: time::Duration) -> String {
     format!("{minutes:.2}m {seconds:.2}s")
 }
 
+/// A running game clock (e.g. `14:03`).
+pub fn clock(elapsed: time::Duration) -> String {
+    let seconds = elapsed.whole_seconds().max(0);
+
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
 pub fn time_since(now: time::OffsetDateTime, since: time::OffsetDateTime) -> String {
     // let now = time::OffsetDateTime::now_utc();
     let duration = now - since;
@@ -125,6 +114,29 @@ pub fn win(result: game::Result) -> String {
     .to_string()
 }
 
+/// The points won or lost in a ranked game (e.g. `+18 LP`, `-20 LP, demoted`).
+pub fn points(delta: summoner::history::Delta) -> String {
+    let movement = match delta.movement {
+        Some(summoner::history::Movement::Promoted) => ", promoted",
+        Some(summoner::history::Movement::Demoted) => ", demoted",
+        None => "",
+    };
+
+    format!("{:+} LP{movement}", delta.points)
+}
+
+/// The placement of an Arena subteam (e.g. `1st`, `3rd`).
+pub fn placement(placement: u8) -> String {
+    let suffix = match placement {
+        1 => "st",
+        2 => "nd",
+        3 => "rd",
+        _ => "th",
+    };
+
+    format!("{placement}{suffix}")
+}
+
 pub fn kda(kills: u32, deaths: u32, assists: u32) -> String {
     if deaths == 0 {
         return String::from("Perfect KDA");
@@ -148,3 +160,19 @@ pub fn creep_score(creep_score: u32, minutes: u32) -> String {
 pub fn vision_score(vision_score: u32) -> String {
     format!("{vision_score} vision")
 }
+
+/// A signed difference, shortened to thousands once it reaches them (e.g. `+1.2k`).
+pub fn difference(value: i64) -> String {
+    let sign = match value.signum() {
+        1 => "+",
+        -1 => "-",
+        _ => "",
+    };
+    let value = value.unsigned_abs();
+
+    if value >= 1000 {
+        format!("{sign}{:.1}k", value as f32 / 1000.0)
+    } else {
+        format!("{sign}{value}")
+    }
+}
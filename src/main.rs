@@ -7,11 +7,13 @@ mod widget;
 use assets::Assets;
 use screen::{profile, search_bar};
 
-use iced::widget::{column, container, horizontal_space, row, text};
+use iced::widget::{button, column, container, horizontal_space, row, text};
 use iced::{Alignment, Element, Length, Task, Theme};
 
 use aery_core as core;
 
+use std::sync::LazyLock;
+
 pub fn main() -> iced::Result {
     #[cfg(target_arch = "wasm32")]
     {
@@ -34,6 +36,7 @@ pub fn main() -> iced::Result {
 
     iced::application(Aery::new, Aery::update, Aery::view)
         .theme(Aery::theme)
+        .subscription(Aery::subscription)
         .title("Aery")
         .window_size([1024.0, 768.0])
         .font(theme::ROBOTO_REGULAR_TTF)
@@ -45,23 +48,56 @@ pub fn main() -> iced::Result {
         .run()
 }
 
+pub fn backend() -> &'static core::Backend {
+    static BACKEND: LazyLock<core::Backend> =
+        LazyLock::new(|| core::Backend::new(dotenv_codegen::dotenv!("WORKER_URL")));
+
+    &BACKEND
+}
+
+pub fn cache() -> Option<&'static core::cache::Store> {
+    static CACHE: LazyLock<Option<core::cache::Store>> = LazyLock::new(|| {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            dirs::data_dir().map(|directory| core::cache::Store::new(directory.join("aery")))
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            None
+        }
+    });
+
+    CACHE.as_ref()
+}
+
 enum Screen {
     Landing(screen::SearchBar),
     Profile(screen::Profile),
+    Failed(profile::Error),
 }
 
 enum Aery {
     Loading,
+    Failed(core::RequestError),
     Loaded { screen: Screen, assets: Assets },
 }
 
 #[derive(Debug, Clone)]
 enum Message {
-    AssetsLoaded(Result<Assets, String>),
-    ProfileLoaded(Result<profile::Data, profile::Error>),
+    AssetsLoaded(Result<Assets, core::RequestError>),
+    AssetsVersionFetched(Result<String, core::RequestError>),
+    AssetsRefreshed(Result<Assets, core::RequestError>),
+    IconsLoaded(Vec<(core::assets::Icon, iced::widget::image::Handle)>),
+    ProfileLoaded(core::Region, Result<profile::Data, profile::Error>),
+    CacheSearched(String, core::Region, Option<profile::Data>),
+    LastProfileLoaded(Option<(core::Region, profile::Data)>),
 
     Profile(profile::Message),
     Landing(search_bar::Message),
+
+    RetryPressed,
+    BackPressed,
 }
 
 impl Aery {
@@ -69,18 +105,21 @@ impl Aery {
         (Self::Loading, Assets::load())
     }
 
-    fn with_assets(assets: Assets) -> Self {
-        Self::Loaded {
-            screen: Screen::Landing(screen::SearchBar::new()),
-            assets,
-        }
+    fn with_assets(assets: Assets) -> (Self, Task<Message>) {
+        let screen = Screen::Landing(screen::SearchBar::new());
+
+        (
+            Self::Loaded { screen, assets },
+            Task::perform(profile::last(), Message::LastProfileLoaded),
+        )
     }
 
     fn theme(&self) -> Theme {
         match self {
             Self::Loading
+            | Self::Failed(_)
             | Self::Loaded {
-                screen: Screen::Landing(_),
+                screen: Screen::Landing(_) | Screen::Failed(_),
                 ..
             } => Theme::Moonfly,
             Self::Loaded {
@@ -90,24 +129,142 @@ impl Aery {
         }
     }
 
+    fn subscription(&self) -> iced::Subscription<Message> {
+        match self {
+            Self::Loaded {
+                screen: Screen::Profile(profile),
+                ..
+            } => profile.subscription().map(Message::Profile),
+            _ => iced::Subscription::none(),
+        }
+    }
+
     fn update(&mut self, message: Message) -> Task<Message> {
+        let task = self.handle(message);
+
+        // Icons looked up while handling the message are loaded in the background
+        match self {
+            Self::Loaded { assets, .. } => {
+                Task::batch([task, assets.load_missing(), assets.check_unknown()])
+            }
+            _ => task,
+        }
+    }
+
+    fn handle(&mut self, message: Message) -> Task<Message> {
         match message {
             Message::AssetsLoaded(Ok(assets)) => {
                 tracing::info!("assets loaded!");
-                *self = Self::with_assets(assets);
+                let (aery, task) = Self::with_assets(assets);
+                *self = aery;
+
+                Task::batch([task, Assets::check_version()])
+            }
+            Message::AssetsLoaded(Err(error)) => {
+                tracing::error!("assets load failed: {error:?}");
+                *self = Self::Failed(error);
+                Task::none()
+            }
+            Message::AssetsVersionFetched(Ok(version)) => {
+                let Self::Loaded { assets, .. } = self else {
+                    return Task::none();
+                };
+
+                if !assets.is_outdated(&version) {
+                    assets.finish_refresh();
+                    return Task::none();
+                }
+
+                tracing::info!("Refreshing assets ({} -> {version})", assets.version());
+                Assets::refresh(version)
+            }
+            Message::AssetsRefreshed(Ok(refreshed)) => {
+                if let Self::Loaded { screen, assets } = self {
+                    tracing::info!("assets refreshed!");
+                    *assets = refreshed;
+                    assets.finish_refresh();
+
+                    if let Screen::Profile(profile) = screen {
+                        profile.rebuild_games(assets);
+                    }
+                }
+
                 Task::none()
             }
-            Message::AssetsLoaded(Err(error)) => panic!("assets load failed: {error:?}"),
-            Message::ProfileLoaded(Ok(profile)) => {
+            Message::IconsLoaded(icons) => {
                 let Self::Loaded { screen, assets } = self else {
                     return Task::none();
                 };
 
-                *screen = Screen::Profile(screen::Profile::from_profile(assets, profile));
+                assets.insert_icons(icons);
+
+                if let Screen::Profile(profile) = screen {
+                    profile.rebuild_games(assets);
+                }
+
+                Task::none()
+            }
+            Message::AssetsVersionFetched(Err(error)) | Message::AssetsRefreshed(Err(error)) => {
+                tracing::warn!("assets refresh failed: {error:?}");
+
+                if let Self::Loaded { assets, .. } = self {
+                    assets.finish_refresh();
+                }
+
+                Task::none()
+            }
+            Message::ProfileLoaded(region, Ok(profile)) => {
+                let Self::Loaded { screen, assets } = self else {
+                    return Task::none();
+                };
+
+                let (profile, task) = screen::Profile::from_profile(assets, region, profile);
+                *screen = Screen::Profile(profile);
+
+                task.map(Message::Profile)
+            }
+            Message::CacheSearched(_, region, Some(cached)) => {
+                let Self::Loaded { screen, assets } = self else {
+                    return Task::none();
+                };
+
+                let (profile, task) = screen::Profile::from_profile(assets, region, cached);
+                let task = Task::batch([task, profile.fetch_latest_games()]).map(Message::Profile);
+                *screen = Screen::Profile(profile);
+
+                task
+            }
+            Message::LastProfileLoaded(Some((region, last))) => {
+                // A search may have been made while the cache was read
+                let Self::Loaded {
+                    screen: screen @ Screen::Landing(_),
+                    assets,
+                } = self
+                else {
+                    return Task::none();
+                };
+
+                let (profile, task) = screen::Profile::from_profile(assets, region, last);
+                let task = Task::batch([task, profile.fetch_latest_games()]).map(Message::Profile);
+                *screen = Screen::Profile(profile);
+
+                task
+            }
+            Message::LastProfileLoaded(None) => Task::none(),
+            Message::CacheSearched(riot_id, region, None) => {
+                Task::perform(profile::fetch(riot_id, region), move |result| {
+                    Message::ProfileLoaded(region, result)
+                })
+            }
+            Message::ProfileLoaded(_, Err(error)) => {
+                tracing::error!("profile load failed: {error:?}");
+
+                if let Self::Loaded { screen, .. } = self {
+                    *screen = Screen::Failed(error);
+                }
 
                 Task::none()
             }
-            Message::ProfileLoaded(Err(error)) => panic!("profile load failed: {error:?}"),
             Message::Profile(message) => {
                 let Self::Loaded { screen, assets } = self else {
                     return Task::none();
@@ -129,12 +286,23 @@ impl Aery {
                         search_bar.update(message)
                     {
                         return Task::perform(
-                            profile::fetch(riot_id, region),
-                            Message::ProfileLoaded,
+                            profile::cached(riot_id.clone(), region),
+                            move |cached| Message::CacheSearched(riot_id, region, cached),
                         );
                     }
                 }
 
+                Task::none()
+            }
+            Message::RetryPressed => {
+                *self = Self::Loading;
+                Assets::load()
+            }
+            Message::BackPressed => {
+                if let Self::Loaded { screen, .. } = self {
+                    *screen = Screen::Landing(screen::SearchBar::new());
+                }
+
                 Task::none()
             }
         }
@@ -143,8 +311,20 @@ impl Aery {
     fn view(&self) -> Element<'_, Message> {
         match self {
             Self::Loading => loading(),
+            Self::Failed(error) => failed(
+                "Failed to load assets",
+                error,
+                "Try again",
+                Message::RetryPressed,
+            ),
             Self::Loaded { screen, .. } => match screen {
                 Screen::Profile(profile) => profile.view().map(Message::Profile),
+                Screen::Failed(error) => failed(
+                    "Failed to load profile",
+                    error,
+                    "Back to search",
+                    Message::BackPressed,
+                ),
                 Screen::Landing(search_bar) => container(
                     column![
                         text("Aery").size(48),
@@ -173,3 +353,33 @@ fn loading<'a>() -> Element<'a, Message> {
         .center_y(Length::Fill)
         .into()
 }
+
+fn failed<'a>(
+    title: &'a str,
+    error: &core::RequestError,
+    action: &'a str,
+    on_press: Message,
+) -> Element<'a, Message> {
+    let reason = match error {
+        core::RequestError::NotFound => {
+            String::from("Nothing was found, check the name and region.")
+        }
+        error => error.to_string(),
+    };
+
+    container(
+        column![
+            text(title).font(theme::BOLD).size(24),
+            text(reason).style(theme::text).size(14),
+            button(text(action).size(14))
+                .style(theme::update)
+                .on_press(on_press),
+        ]
+        .spacing(8)
+        .align_x(Alignment::Center),
+    )
+    .style(theme::timeline)
+    .center_x(Length::Fill)
+    .center_y(Length::Fill)
+    .into()
+}
@@ -1,6 +1,9 @@
 mod game;
 use game::Game;
 
+mod live;
+use live::Live;
+
 mod ranked_overview;
 use ranked_overview::RankedOverview;
 
@@ -11,6 +14,7 @@ mod summoner;
 use summoner::Summoner;
 
 use crate::core;
+use crate::core::backend;
 use crate::core::game::Queue;
 use crate::screen::search_bar::{self, SearchBar};
 use crate::theme;
@@ -21,12 +25,18 @@ use iced::widget::{
     button, column, container, horizontal_space, pick_list, row, scrollable, text, themer,
     vertical_space,
 };
-use iced::{Alignment, Element, Length, Task, Theme};
+use iced::{Alignment, Element, Length, Subscription, Task, Theme};
 use iced::{border, padding};
 
 use itertools::Itertools;
 
-pub type Error = String;
+use std::collections::HashMap;
+use std::time::Duration;
+
+pub type Error = core::RequestError;
+
+/// How many of the newest games have their lobbies ranked when shown.
+const RANKED_GAMES: usize = core::game::Map::PAGE_SIZE as usize;
 
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum QueueFilter {
@@ -36,17 +46,46 @@ pub enum QueueFilter {
 }
 
 impl QueueFilter {
-    pub const ALTERNATIVE: [QueueFilter; 7] = [
+    pub const ALTERNATIVE: [QueueFilter; 16] = [
         QueueFilter::Specific(Queue::Custom),
         QueueFilter::Specific(Queue::Blind),
         QueueFilter::Specific(Queue::Draft),
+        QueueFilter::Specific(Queue::Swiftplay),
+        QueueFilter::Specific(Queue::Quickplay),
         QueueFilter::Specific(Queue::Clash),
+        QueueFilter::Specific(Queue::Arena),
+        QueueFilter::Specific(Queue::ARAMMayhem),
+        QueueFilter::Specific(Queue::URF),
+        QueueFilter::Specific(Queue::ARURF),
+        QueueFilter::Specific(Queue::OneForAll),
+        QueueFilter::Specific(Queue::NexusBlitz),
+        QueueFilter::Specific(Queue::UltimateSpellbook),
         QueueFilter::Specific(Queue::BotIntro),
         QueueFilter::Specific(Queue::BotBeginner),
         QueueFilter::Specific(Queue::BotIntermediate),
     ];
 }
 
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tab {
+    #[default]
+    History,
+    Live,
+}
+
+impl Tab {
+    const ALL: [Tab; 2] = [Tab::History, Tab::Live];
+}
+
+impl std::fmt::Display for Tab {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Tab::History => "Match history",
+            Tab::Live => "Live game",
+        })
+    }
+}
+
 impl PartialEq<Queue> for QueueFilter {
     fn eq(&self, other: &Queue) -> bool {
         match self {
@@ -67,17 +106,29 @@ impl std::fmt::Display for QueueFilter {
 
 #[derive(Debug, Clone)]
 pub enum Message {
-    FetchedData(Result<Data, String>),
-    FetchedGames(Result<core::game::Map, String>),
+    FetchedData(Result<Data, Error>),
+    /// A page of games of a puuid, which may not be the summoner shown anymore.
+    FetchedGames(String, Result<core::game::Map, Error>),
+    FetchedTimeline(core::game::Id, Result<core::game::Timeline, Error>),
+    FetchedRanks(Result<core::summoner::Ranks, Error>),
+    /// The game in progress of a puuid, which may not be the summoner shown anymore.
+    FetchedLiveGame(String, Result<Option<core::live::Game>, Error>),
+    Restored(Cached),
+    CacheSearched(String, core::Region, Option<Data>),
 
     Game(usize, game::Message),
     Summoner(summoner::Message),
     SearchBar(search_bar::Message),
     RankedOverview(ranked_overview::Message),
+    Live(live::Message),
 
     FetchGames(i64),
+    TabSelected(Tab),
+    RefreshLiveGame,
+    ClockTicked,
     QueueFilterChanged(QueueFilter),
     ThemeChanged(Theme),
+    ErrorDismissed,
 }
 
 #[derive(Debug, Clone)]
@@ -85,36 +136,91 @@ pub struct Profile {
     puuid: String,
     region: core::Region,
     queue_filter: QueueFilter,
+    tab: Tab,
 
     summary: Summary,
+    history: core::game::Map,
+    leagues: core::summoner::History,
+    /// The leagues of the players met in games, shared by every profile of the region.
+    ranks: core::summoner::Ranks,
+    timelines: HashMap<core::game::Id, core::game::Timeline>,
     games: Vec<Game>,
     summoner: Summoner,
     search_bar: SearchBar,
     ranked_overview: RankedOverview,
+    live: Live,
     theme: Theme,
+    error: Option<Error>,
+    /// Whether the cached games and leagues were merged in, nothing is saved before so the
+    /// cache is never overwritten by a partial history.
+    restored: bool,
+}
+
+/// The games and leagues of a summoner read from the cache.
+#[derive(Debug, Clone)]
+pub struct Cached {
+    puuid: String,
+    history: core::game::Map,
+    leagues: core::summoner::History,
+    ranks: core::summoner::Ranks,
 }
 
 impl Profile {
-    pub fn from_profile(assets: &mut crate::Assets, profile: Data) -> Self {
+    /// Shows the `profile` right away, while its cached games and leagues are restored.
+    pub fn from_profile(
+        assets: &mut crate::Assets,
+        region: core::Region,
+        profile: Data,
+    ) -> (Self, Task<Message>) {
         let puuid = profile.summoner.puuid().to_owned();
-        let games = profile
-            .games
+        let history = latest_games(&profile);
+
+        let mut leagues = core::summoner::History::default();
+        leagues.record(now(), &profile.leagues);
+
+        let ranks = core::summoner::Ranks::default();
+        let points = leagues.deltas(&history);
+        let ranked_overview = RankedOverview::from_profile(assets, &profile, &leagues);
+        let games = history
             .iter()
-            .map(|game| Game::from_summoner_game(assets, &puuid, game))
+            .map(|(id, game)| {
+                Game::from_summoner_game(
+                    assets,
+                    &puuid,
+                    game,
+                    points.get(id).copied(),
+                    ranks.average(game),
+                )
+            })
             .collect_vec();
         let summary = Summary::from_games(assets, &games);
-
-        Self {
-            region: core::Region::default(),
+        let summoner = Summoner::from_profile(&profile);
+        let task = Task::batch([
+            Task::future(save_profile(region, profile)).discard(),
+            Task::perform(restore(region, puuid.clone()), Message::Restored),
+        ]);
+
+        let profile = Self {
+            region,
             queue_filter: QueueFilter::default(),
+            tab: Tab::default(),
             summary,
+            history,
+            leagues,
+            ranks,
+            timelines: HashMap::new(),
             games,
             search_bar: SearchBar::new(),
-            summoner: Summoner::from_profile(&profile),
-            ranked_overview: RankedOverview::from_profile(assets, &profile),
+            summoner,
+            ranked_overview,
+            live: Live::default(),
             theme: Theme::Moonfly,
+            error: None,
+            restored: false,
             puuid,
-        }
+        };
+
+        (profile, task)
     }
 
     pub fn update(&mut self, message: Message, assets: &mut crate::Assets) -> Task<Message> {
@@ -124,51 +230,187 @@ impl Profile {
             }
             Message::QueueFilterChanged(new_filter) => {
                 self.queue_filter = new_filter;
+
+                return self.fetch_ranks();
+            }
+            Message::TabSelected(tab) => {
+                self.tab = tab;
+
+                if tab == Tab::Live && !matches!(self.live, Live::Loading) {
+                    self.live = Live::Loading;
+
+                    return self.fetch_live_game();
+                }
+            }
+            Message::RefreshLiveGame => {
+                return self.fetch_live_game();
             }
+            // Handling the message redraws the clock of the live game
+            Message::ClockTicked => {}
+            // The summoner changed while the game was requested
+            Message::FetchedLiveGame(puuid, _) if puuid != self.puuid => {}
+            Message::FetchedLiveGame(_, Ok(Some(game))) => {
+                let players = game
+                    .players
+                    .iter()
+                    .filter_map(|player| player.puuid.clone())
+                    .collect_vec();
+                self.live = Live::Playing(live::Game::new(assets, &self.puuid, game, &self.ranks));
+
+                return self.fetch_leagues(players.iter().map(String::as_str));
+            }
+            Message::FetchedLiveGame(_, Ok(None)) => {
+                self.live = Live::Idle;
+            }
+            Message::FetchedLiveGame(_, Err(error)) => {
+                tracing::warn!("live game request failed: {error:?}");
+                self.live = Live::Failed;
+            }
+            Message::Live(message) => match self.live.update(message) {
+                Some(live::Event::NamePressed(riot_id)) => {
+                    return Task::perform(
+                        crate::backend().summoner(self.region, riot_id),
+                        Message::FetchedData,
+                    );
+                }
+                None => {}
+            },
             Message::FetchGames(start_time) => {
+                let puuid = self.puuid.clone();
+
                 return Task::perform(
-                    fetch_games(self.puuid.clone(), self.region, Some(start_time)),
-                    Message::FetchedGames,
+                    fetch_games(
+                        puuid.clone(),
+                        self.region,
+                        backend::Cursor::Before(start_time),
+                    ),
+                    move |result| Message::FetchedGames(puuid, result),
                 );
             }
-            Message::FetchedGames(Ok(games)) => {
-                self.games.extend(
-                    games
-                        .iter()
-                        .map(|(_, game)| Game::from_summoner_game(assets, &self.puuid, game)),
-                );
-                self.summary = Summary::from_games(assets, &self.games);
+            Message::FetchedGames(puuid, _) if puuid != self.puuid => {}
+            Message::FetchedGames(_, Ok(games)) => {
+                self.error = None;
+                let players = games
+                    .iter()
+                    .flat_map(|(_, game)| &game.players)
+                    .map(|player| player.puuid.clone())
+                    .collect_vec();
+                let gap = self.history.merge(games);
+                self.rebuild_games(assets);
+
+                return Task::batch([
+                    self.fetch_gap(gap),
+                    self.save_games(),
+                    self.fetch_leagues(players.iter().map(String::as_str)),
+                ]);
             }
             Message::FetchedData(Ok(profile)) => {
-                self.puuid = profile.summoner.puuid().to_owned();
+                self.error = None;
+                let mut tasks = Vec::new();
+
+                if profile.summoner.puuid() != self.puuid {
+                    self.puuid = profile.summoner.puuid().to_owned();
+                    self.history = core::game::Map::default();
+                    self.leagues = core::summoner::History::default();
+                    self.ranks = core::summoner::Ranks::default();
+                    self.restored = false;
+                    self.live = Live::default();
+                    self.tab = Tab::History;
+
+                    tasks.push(Task::perform(
+                        restore(self.region, self.puuid.clone()),
+                        Message::Restored,
+                    ));
+                }
+
+                let gap = self.history.merge(latest_games(&profile));
+                self.leagues.record(now(), &profile.leagues);
+
                 self.summoner = Summoner::from_profile(&profile);
-                self.games = profile
-                    .games
-                    .iter()
-                    .map(|game| Game::from_summoner_game(assets, profile.summoner.puuid(), game))
-                    .collect();
-                self.summary = Summary::from_games(assets, &self.games);
-                self.ranked_overview = RankedOverview::from_profile(assets, &profile);
+                let mut ranked_overview =
+                    RankedOverview::from_profile(assets, &profile, &self.leagues);
+                ranked_overview.restore(&self.ranked_overview);
+                self.ranked_overview = ranked_overview;
+                self.rebuild_games(assets);
+
+                tasks.extend([
+                    Task::future(save_profile(self.region, profile)).discard(),
+                    self.fetch_gap(gap),
+                    self.save_games(),
+                    self.save_leagues(),
+                    self.fetch_ranks(),
+                ]);
+
+                return Task::batch(tasks);
+            }
+            Message::Restored(cached) => {
+                if cached.puuid != self.puuid {
+                    return Task::none();
+                }
+
+                let fetched = std::mem::replace(&mut self.history, cached.history);
+                let gap = self.history.merge(fetched);
+
+                let recorded = std::mem::replace(&mut self.leagues, cached.leagues);
+                self.leagues.merge(recorded);
+
+                let fetched = std::mem::replace(&mut self.ranks, cached.ranks);
+                self.ranks.merge(fetched);
+
+                self.restored = true;
+                self.ranked_overview.set_history(assets, &self.leagues);
+                self.rebuild_games(assets);
+
+                return Task::batch([
+                    self.fetch_gap(gap),
+                    self.save_games(),
+                    self.save_leagues(),
+                    self.save_ranks(),
+                    self.fetch_ranks(),
+                ]);
+            }
+            Message::FetchedData(Err(error)) | Message::FetchedGames(_, Err(error)) => {
+                tracing::error!("profile request failed: {error:?}");
+                self.error = Some(error);
+            }
+            Message::ErrorDismissed => {
+                self.error = None;
+            }
+            Message::FetchedTimeline(_, Ok(timeline)) => {
+                self.insert_timeline(assets, timeline);
+            }
+            Message::FetchedRanks(Ok(ranks)) => {
+                self.ranks.merge(ranks);
+                self.rebuild_games(assets);
+
+                return self.save_ranks();
+            }
+            Message::FetchedRanks(Err(error)) => {
+                tracing::warn!("ranks request failed: {error:?}");
+            }
+            Message::FetchedTimeline(id, Err(error)) => {
+                tracing::warn!("timeline request failed: {error:?}");
+
+                for game in self.games.iter_mut().filter(|game| game.id() == &id) {
+                    game.timeline_failed();
+                }
             }
-            Message::FetchedData(Err(error)) => panic!("failed: {error}"),
-            Message::FetchedGames(Err(error)) => panic!("failed: {error}"),
             Message::Game(index, message) => {
-                if let Some(game::Event::NamePressed(riot_id)) = self
+                match self
                     .games
                     .get_mut(index)
                     .and_then(|game| game.update(message))
                 {
-                    return riot_id
-                        .name
-                        .as_ref()
-                        .zip(riot_id.tagline.as_ref())
-                        .map(|(name, tag)| {
-                            Task::perform(
-                                fetch(format!("{name}#{tag}"), self.region),
-                                Message::FetchedData,
-                            )
-                        })
-                        .unwrap_or(Task::none());
+                    Some(game::Event::NamePressed(riot_id)) => {
+                        return Task::perform(
+                            crate::backend().summoner(self.region, riot_id),
+                            Message::FetchedData,
+                        );
+                    }
+                    Some(game::Event::TimelineRequested(id)) => {
+                        return self.load_timeline(assets, id);
+                    }
+                    None => {}
                 }
             }
             Message::Summoner(message) => {
@@ -176,10 +418,7 @@ impl Profile {
                     match event {
                         summoner::Event::UpdateProfile(name) => {
                             if self.games.is_empty() {
-                                return Task::perform(
-                                    fetch_games(self.puuid.clone(), self.region, None),
-                                    Message::FetchedGames,
-                                );
+                                return self.fetch_latest_games();
                             }
 
                             return Task::perform(fetch(name, self.region), Message::FetchedData);
@@ -191,19 +430,215 @@ impl Profile {
                 if let Some(event) = self.search_bar.update(message) {
                     match event {
                         search_bar::Event::SearchRequested { riot_id, region } => {
-                            self.region = region;
-
-                            return Task::perform(fetch(riot_id, region), Message::FetchedData);
+                            return Task::perform(cached(riot_id.clone(), region), move |cached| {
+                                Message::CacheSearched(riot_id, region, cached)
+                            });
                         }
                     }
                 }
             }
+            Message::CacheSearched(_, region, Some(profile)) => {
+                let (profile, task) = Self::from_profile(assets, region, profile);
+                *self = Self {
+                    theme: self.theme.clone(),
+                    ..profile
+                };
+
+                return Task::batch([task, self.fetch_latest_games()]);
+            }
+            Message::CacheSearched(riot_id, region, None) => {
+                self.region = region;
+
+                return Task::perform(fetch(riot_id, region), Message::FetchedData);
+            }
             Message::RankedOverview(message) => self.ranked_overview.update(message),
         }
 
         Task::none()
     }
 
+    /// Requests the games played since the most recent known game.
+    pub fn fetch_latest_games(&self) -> Task<Message> {
+        let cursor = self
+            .history
+            .newest()
+            .map(|game| backend::Cursor::After(game.created_at / 1000 + 1))
+            .unwrap_or(backend::Cursor::Latest);
+        let puuid = self.puuid.clone();
+
+        Task::perform(
+            fetch_games(puuid.clone(), self.region, cursor),
+            move |result| Message::FetchedGames(puuid, result),
+        )
+    }
+
+    /// Requests the game the summoner is currently playing.
+    fn fetch_live_game(&self) -> Task<Message> {
+        let puuid = self.puuid.clone();
+
+        Task::perform(
+            crate::backend().live_game(self.region, puuid.clone()),
+            move |result| Message::FetchedLiveGame(puuid, result),
+        )
+    }
+
+    /// Refreshes the live game periodically while its tab is open, and runs its clock.
+    pub fn subscription(&self) -> Subscription<Message> {
+        if self.tab != Tab::Live {
+            return Subscription::none();
+        }
+
+        let refresh = iced::time::every(Duration::from_secs(60)).map(|_| Message::RefreshLiveGame);
+
+        if self.live.is_playing() {
+            Subscription::batch([
+                refresh,
+                iced::time::every(Duration::from_secs(1)).map(|_| Message::ClockTicked),
+            ])
+        } else {
+            refresh
+        }
+    }
+
+    /// Requests the games filling the `gap` left in the history by a merged page.
+    fn fetch_gap(&self, gap: Option<backend::Cursor>) -> Task<Message> {
+        let Some(cursor) = gap else {
+            return Task::none();
+        };
+        let puuid = self.puuid.clone();
+
+        Task::perform(
+            fetch_games(puuid.clone(), self.region, cursor),
+            move |result| Message::FetchedGames(puuid, result),
+        )
+    }
+
+    /// Requests the leagues of the players of the newest games shown, whose lobbies cannot be
+    /// ranked yet.
+    ///
+    /// Older games are ranked as their pages are fetched, cached games by the leagues known.
+    fn fetch_ranks(&self) -> Task<Message> {
+        self.fetch_leagues(
+            self.history
+                .iter()
+                .filter(|(_, game)| self.queue_filter == game.queue)
+                .take(RANKED_GAMES)
+                .flat_map(|(_, game)| &game.players)
+                .map(|player| player.puuid.as_str()),
+        )
+    }
+
+    /// Requests the leagues of the `puuids` that are unknown or stale, in batches.
+    fn fetch_leagues<'a>(&self, puuids: impl IntoIterator<Item = &'a str>) -> Task<Message> {
+        // Every player would be missing until the cached leagues are restored
+        if !self.restored {
+            return Task::none();
+        }
+
+        let missing = self.ranks.missing(puuids);
+
+        Task::batch(missing.chunks(core::summoner::ranks::BATCH).map(|puuids| {
+            Task::perform(
+                crate::backend().ranks(self.region, puuids.to_vec()),
+                Message::FetchedRanks,
+            )
+        }))
+    }
+
+    /// Shows the timeline of a game, loading it unless it was already.
+    fn load_timeline(&mut self, assets: &crate::Assets, id: core::game::Id) -> Task<Message> {
+        if let Some(timeline) = self.timelines.get(&id).cloned() {
+            self.insert_timeline(assets, timeline);
+
+            return Task::none();
+        }
+
+        Task::perform(timeline(self.region, id.clone()), move |result| {
+            Message::FetchedTimeline(id, result)
+        })
+    }
+
+    fn save_games(&self) -> Task<Message> {
+        if !self.restored {
+            return Task::none();
+        }
+
+        Task::future(save_games(
+            self.region,
+            self.puuid.clone(),
+            self.history.clone(),
+        ))
+        .discard()
+    }
+
+    fn save_leagues(&self) -> Task<Message> {
+        if !self.restored {
+            return Task::none();
+        }
+
+        Task::future(save_leagues(
+            self.region,
+            self.puuid.clone(),
+            self.leagues.clone(),
+        ))
+        .discard()
+    }
+
+    fn save_ranks(&self) -> Task<Message> {
+        if !self.restored {
+            return Task::none();
+        }
+
+        Task::future(save_ranks(self.region, self.ranks.clone())).discard()
+    }
+
+    fn insert_timeline(&mut self, assets: &crate::Assets, timeline: core::game::Timeline) {
+        for game in self
+            .games
+            .iter_mut()
+            .filter(|game| game.id() == &timeline.id)
+        {
+            game.set_timeline(assets, &timeline);
+        }
+
+        self.timelines.insert(timeline.id.clone(), timeline);
+    }
+
+    /// Rebuilds every game, including the live one, keeping their timelines and view state.
+    pub fn rebuild_games(&mut self, assets: &crate::Assets) {
+        let previous = std::mem::take(&mut self.games)
+            .into_iter()
+            .map(|game| (game.id().clone(), game))
+            .collect::<HashMap<_, _>>();
+        let points = self.leagues.deltas(&self.history);
+
+        self.games = self
+            .history
+            .iter()
+            .map(|(id, game)| {
+                let mut game = Game::from_summoner_game(
+                    assets,
+                    &self.puuid,
+                    game,
+                    points.get(id).copied(),
+                    self.ranks.average(game),
+                );
+
+                if let Some(timeline) = self.timelines.get(game.id()) {
+                    game.set_timeline(assets, timeline);
+                }
+
+                if let Some(previous) = previous.get(game.id()) {
+                    game.restore(previous);
+                }
+
+                game
+            })
+            .collect();
+        self.summary = Summary::from_games(assets, &self.games);
+        self.live.set_ranks(assets, &self.puuid, &self.ranks);
+    }
+
     pub fn timeline(&self) -> Element<'_, Message> {
         let games = self
             .games
@@ -271,20 +706,52 @@ impl Profile {
             ..theme::dark(theme)
         });
 
-        let content = column![
-            self.summoner.view().map(Message::Summoner),
-            filter_bar(self.queue_filter),
-            row![
-                self.ranked_overview.view().map(Message::RankedOverview),
-                container(self.timeline())
-                    .width(Length::Shrink)
-                    .style(theme::timeline),
-            ]
-            .spacing(8),
-        ]
-        .width(968)
-        .spacing(8)
-        .padding(8);
+        let error = self.error.as_ref().map(|error| {
+            container(
+                row![
+                    text!("Request failed: {error}")
+                        .style(theme::defeat)
+                        .size(12),
+                    horizontal_space().width(Length::Fill),
+                    button(text("Dismiss").size(12))
+                        .style(theme::show_more)
+                        .on_press(Message::ErrorDismissed),
+                ]
+                .align_y(Alignment::Center),
+            )
+            .width(Length::Fill)
+            .padding(8)
+            .style(theme::dark)
+        });
+
+        let mut content = column![self.summoner.view().map(Message::Summoner)];
+
+        if let Some(error) = error {
+            content = content.push(error);
+        }
+
+        content = content.push(tab_bar(self.tab));
+
+        let main = match self.tab {
+            Tab::History => {
+                content = content.push(filter_bar(self.queue_filter));
+
+                self.timeline()
+            }
+            Tab::Live => self.live.view().map(Message::Live),
+        };
+
+        let content = content
+            .push(
+                row![
+                    self.ranked_overview.view().map(Message::RankedOverview),
+                    container(main).width(Length::Shrink).style(theme::timeline),
+                ]
+                .spacing(8),
+            )
+            .width(968)
+            .spacing(8)
+            .padding(8);
 
         container(column![
             top_bar,
@@ -329,6 +796,22 @@ fn theme_picker<'a>(theme: &'a Theme) -> Element<'a, Message> {
     container(content).padding(4).into()
 }
 
+fn tab_bar<'a>(selected: Tab) -> Element<'a, Message> {
+    container(
+        row(Tab::ALL.into_iter().map(|tab| {
+            button(text!("{tab}").size(12))
+                .style(move |theme, status| theme::queue_filter(theme, status, selected == tab))
+                .on_press(Message::TabSelected(tab))
+                .into()
+        }))
+        .spacing(4),
+    )
+    .width(Length::Fill)
+    .padding(8)
+    .style(theme::dark)
+    .into()
+}
+
 fn filter_bar<'a>(selected: QueueFilter) -> Element<'a, Message> {
     let queue_button = |queue: QueueFilter| -> Element<'_, Message> {
         button(text!("{queue}").size(12))
@@ -366,48 +849,113 @@ fn filter_bar<'a>(selected: QueueFilter) -> Element<'a, Message> {
 pub async fn fetch_games(
     puuid: String,
     region: core::Region,
-    start_time: Option<i64>,
-) -> Result<game::Map, String> {
-    use futures::TryFutureExt;
-
-    let worker_url = dotenv_codegen::dotenv!("WORKER_URL");
-    let mut path = format!("{worker_url}/matches/{puuid}");
-    if let Some(time) = start_time {
-        path.push_str(&format!("?end_time={time}"));
+    cursor: backend::Cursor,
+) -> Result<game::Map, Error> {
+    crate::backend().matches(region, puuid, cursor).await
+}
+
+pub async fn fetch(riot_id: String, region: core::Region) -> Result<Data, Error> {
+    crate::backend().summoner(region, riot_id.parse()?).await
+}
+
+pub async fn cached(riot_id: String, region: core::Region) -> Option<Data> {
+    let riot_id = riot_id.parse().ok()?;
+
+    crate::cache()?.profile(region, &riot_id)
+}
+
+/// Reads the profile shown last from the cache.
+pub async fn last() -> Option<(core::Region, Data)> {
+    crate::cache()?.last()
+}
+
+/// Reads the games and leagues of the summoner `puuid` from the cache.
+async fn restore(region: core::Region, puuid: String) -> Cached {
+    let Some(cache) = crate::cache() else {
+        return Cached {
+            puuid,
+            history: core::game::Map::default(),
+            leagues: core::summoner::History::default(),
+            ranks: core::summoner::Ranks::default(),
+        };
+    };
+
+    Cached {
+        history: cache.games(region, &puuid),
+        leagues: cache.leagues(region, &puuid),
+        ranks: cache.ranks(region),
+        puuid,
+    }
+}
+
+/// Reads the timeline of a game from the cache, or requests it if it was never fetched.
+async fn timeline(region: core::Region, id: core::game::Id) -> Result<core::game::Timeline, Error> {
+    if let Some(timeline) = crate::cache().and_then(|cache| cache.timeline(region, &id)) {
+        return Ok(timeline);
     }
 
-    tracing::info!("Requesting `{puuid}` ({region}) to {path}");
+    let timeline = crate::backend().timeline(region, id).await?;
 
-    reqwest::get(path)
-        .map_err(|e| e.to_string())
-        .await?
-        .bytes()
-        .map_err(|e| e.to_string())
-        .await
-        .map(|bytes| game::Map::decode(&bytes))
+    if let Some(cache) = crate::cache() {
+        if let Err(error) = cache.save_timeline(region, &timeline) {
+            tracing::warn!("Failed to cache timeline: {error}");
+        }
+    }
+
+    Ok(timeline)
+}
+
+fn latest_games(profile: &Data) -> core::game::Map {
+    profile
+        .games
+        .iter()
+        .map(|game| (game.id.clone(), game.clone()))
+        .collect()
+}
+
+/// The current time, in milliseconds since the Unix epoch.
+fn now() -> i64 {
+    (time::OffsetDateTime::now_utc().unix_timestamp_nanos() / 1_000_000) as i64
+}
+
+async fn save_profile(region: core::Region, profile: Data) {
+    let Some(cache) = crate::cache() else {
+        return;
+    };
+
+    if let Err(error) = cache.save_profile(region, &profile) {
+        tracing::warn!("Failed to cache profile: {error}");
+    }
 }
 
-#[cfg(not(feature = "dummy"))]
-pub async fn fetch(name: String, region: core::Region) -> Result<core::summoner::Data, String> {
-    use futures::TryFutureExt;
-
-    let worker_url = dotenv_codegen::dotenv!("WORKER_URL");
-    let path = format!("{worker_url}/summoner/{region}/{}", name.replace("#", "-"));
-    tracing::info!("Requesting `{name}` ({region}) to {path}");
-
-    reqwest::get(path)
-        .map_err(|e| e.to_string())
-        .await?
-        .bytes()
-        .map_err(|e| e.to_string())
-        .await
-        .map(|bytes| core::summoner::Data::decode(&bytes))
+async fn save_games(region: core::Region, puuid: String, history: core::game::Map) {
+    let Some(cache) = crate::cache() else {
+        return;
+    };
+
+    if let Err(error) = cache.save_games(region, &puuid, &history) {
+        tracing::warn!("Failed to cache games: {error}");
+    }
 }
 
-#[cfg(feature = "dummy")]
-pub async fn fetch(name: String, _region: core::Region) -> Result<core::summoner::Data, String> {
-    let mut id = name.split("#");
-    let (name, tag) = (id.next().unwrap_or("someone"), id.next().unwrap_or("foo"));
+/// Saves the snapshots of the leagues of the summoner, to infer the points won or lost in its
+/// games.
+async fn save_leagues(region: core::Region, puuid: String, leagues: core::summoner::History) {
+    let Some(cache) = crate::cache() else {
+        return;
+    };
 
-    Ok(core::summoner::Data::dummy(name, tag))
+    if let Err(error) = cache.save_leagues(region, &puuid, &leagues) {
+        tracing::warn!("Failed to cache leagues: {error}");
+    }
+}
+
+async fn save_ranks(region: core::Region, ranks: core::summoner::Ranks) {
+    let Some(cache) = crate::cache() else {
+        return;
+    };
+
+    if let Err(error) = cache.save_ranks(region, &ranks) {
+        tracing::warn!("Failed to cache ranks: {error}");
+    }
 }
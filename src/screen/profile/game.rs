@@ -1,6 +1,19 @@
+mod build;
+use build::Builds;
+
+mod chart;
+use chart::Chart;
+
+mod minimap;
+use minimap::Minimap;
+
+mod stats;
+
 use crate::core;
 use crate::core::game;
 use crate::core::game::item;
+use crate::core::summoner::Tier;
+use crate::core::summoner::history::Delta;
 use crate::formatting;
 use crate::theme;
 use crate::theme::icon;
@@ -136,30 +149,113 @@ impl Player {
 struct Team {
     id: core::Team,
     result: game::Result,
+    objectives: game::team::Objectives,
+    bans: Vec<Ban>,
     players: Vec<Player>,
 }
 
+/// The players of an Arena subteam.
+#[derive(Debug, Clone)]
+struct Subteam {
+    placement: u8,
+    players: Vec<Player>,
+}
+
+#[derive(Debug, Clone)]
+struct Ban {
+    champion: core::Champion,
+    icon: image::Handle,
+}
+
+/// A player, as drawn on the timeline.
+#[derive(Debug, Clone)]
+struct Participant {
+    puuid: String,
+    team: core::Team,
+    champion: image::Handle,
+}
+
+#[derive(Debug, Clone, Default)]
+enum Timeline {
+    #[default]
+    Unrequested,
+    Loading,
+    Loaded {
+        chart: Chart,
+        minimap: Minimap,
+        builds: Builds,
+    },
+    Unavailable,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Tab {
+    #[default]
+    Overview,
+    Timeline,
+    Builds,
+    Stats,
+}
+
+impl Tab {
+    const ALL: [Tab; 4] = [Tab::Overview, Tab::Timeline, Tab::Builds, Tab::Stats];
+}
+
+impl std::fmt::Display for Tab {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Tab::Overview => "Overview",
+            Tab::Timeline => "Timeline",
+            Tab::Builds => "Builds",
+            Tab::Stats => "Stats",
+        })
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Game {
+    id: game::Id,
     result: game::Result,
     queue: game::Queue,
     time: time::OffsetDateTime,
     duration: time::Duration,
     player: Player,
     teams: Vec<Team>,
+    /// The subteams of an Arena game, by placement.
+    subteams: Vec<Subteam>,
+    /// The players in the order of the game, which may differ from the timeline.
+    participants: Vec<Participant>,
+    timeline: Timeline,
+    stats: stats::Table,
+    /// The points won or lost, when they could be inferred.
+    points: Option<Delta>,
+    /// The average rank of the players, once their leagues are known.
+    lobby: Option<Lobby>,
 
     is_expanded: bool,
+    tab: Tab,
+}
+
+#[derive(Debug, Clone)]
+struct Lobby {
+    tier: Tier,
+    emblem: image::Handle,
 }
 
 #[derive(Debug, Clone)]
 pub enum Message {
     ExpandPressed,
     NamePressed(account::RiotId),
+    TabSelected(Tab),
+    Minimap(minimap::Message),
+    Builds(build::Message),
+    Stats(stats::Message),
 }
 
 #[derive(Debug, Clone)]
 pub enum Event {
     NamePressed(account::RiotId),
+    TimelineRequested(game::Id),
 }
 
 impl Game {
@@ -167,41 +263,151 @@ impl Game {
         assets: &crate::assets::Assets,
         puuid: &str,
         game: &core::Game,
+        points: Option<Delta>,
+        lobby: Option<Tier>,
     ) -> Self {
         let player = game.player(puuid).unwrap();
         let player = Player::from_participant(assets, player);
 
         let teams = game
-            .players
+            .teams
             .iter()
-            .into_grouping_map_by(|p| p.team)
-            .fold(Vec::new(), |mut players, _team, participant| {
-                players.push(Player::from_participant(assets, participant));
-                players
+            .map(|team| Team {
+                id: team.id,
+                result: team.result,
+                objectives: team.objectives,
+                bans: team
+                    .bans
+                    .iter()
+                    .sorted_by_key(|ban| ban.pick_turn)
+                    .map(|ban| Ban {
+                        champion: ban.champion,
+                        icon: assets.champion(&ban.champion),
+                    })
+                    .collect(),
+                players: game
+                    .players
+                    .iter()
+                    .filter(|participant| participant.team == team.id)
+                    .map(|participant| Player::from_participant(assets, participant))
+                    .collect(),
             })
-            .into_iter()
-            .map(|(id, players)| Team {
-                id,
-                result: players
-                    .first()
-                    .map(|p| p.info.result)
-                    .unwrap_or(game::Result::Defeat), // TODO: fix this
-                players,
+            .collect();
+
+        let subteams = game
+            .players
+            .iter()
+            .filter_map(|participant| Some((participant.arena.as_ref()?, participant)))
+            .into_group_map_by(|(arena, _)| arena.subteam)
+            .into_values()
+            .map(|players| Subteam {
+                placement: players[0].0.placement,
+                players: players
+                    .into_iter()
+                    .map(|(_, participant)| Player::from_participant(assets, participant))
+                    .collect(),
             })
+            .sorted_by_key(|subteam| subteam.placement)
             .collect();
 
         Game {
+            id: game.id.clone(),
             result: player.info.result,
             queue: game.queue,
             time: game.created_at_time(),
             duration: game.duration_time(),
             player,
             teams,
+            subteams,
+            participants: game
+                .players
+                .iter()
+                .map(|player| Participant {
+                    puuid: player.puuid.clone(),
+                    team: player.team,
+                    champion: assets.champion(&player.champion),
+                })
+                .collect(),
+            timeline: Timeline::default(),
+            stats: stats::Table::default(),
+            points,
+            lobby: lobby.map(|tier| Lobby {
+                tier,
+                emblem: assets.emblem(&tier),
+            }),
 
             is_expanded: false,
+            tab: Tab::default(),
         }
     }
 
+    pub fn id(&self) -> &game::Id {
+        &self.id
+    }
+
+    /// Keeps the view state of the same game before it was rebuilt.
+    pub fn restore(&mut self, previous: &Game) {
+        self.is_expanded = previous.is_expanded;
+        self.tab = previous.tab;
+        self.stats = previous.stats;
+
+        if let Timeline::Unrequested = self.timeline {
+            self.timeline = previous.timeline.clone();
+        } else if let (
+            Timeline::Loaded {
+                minimap, builds, ..
+            },
+            Timeline::Loaded {
+                minimap: previous_minimap,
+                builds: previous_builds,
+                ..
+            },
+        ) = (&mut self.timeline, &previous.timeline)
+        {
+            minimap.restore(previous_minimap);
+            builds.restore(previous_builds);
+        }
+    }
+
+    pub fn set_timeline(&mut self, assets: &crate::Assets, timeline: &game::Timeline) {
+        // Timeline participants are numbered in their own order, matched to players by puuid
+        let Some(participants) = timeline
+            .participants
+            .iter()
+            .map(|puuid| {
+                self.participants
+                    .iter()
+                    .find(|participant| &participant.puuid == puuid)
+                    .cloned()
+            })
+            .collect::<Option<Vec<_>>>()
+        else {
+            tracing::warn!(
+                "timeline participants of `{}` are not its players",
+                self.id.as_ref()
+            );
+            self.timeline = Timeline::Unavailable;
+
+            return;
+        };
+
+        let team = self.player.info.team;
+        // Without the summoner, no build is selected until a player is picked
+        let player = timeline
+            .participant(&self.player.info.puuid)
+            .map(|participant| participant.index());
+
+        self.timeline = Timeline::Loaded {
+            chart: Chart::new(timeline, &participants, team),
+            minimap: Minimap::new(timeline, &participants, team),
+            builds: Builds::new(assets, timeline, &participants, player),
+        };
+    }
+
+    pub fn timeline_failed(&mut self) {
+        self.timeline = Timeline::Unavailable;
+    }
+
     pub fn started_at(&self) -> time::OffsetDateTime {
         self.time
     }
@@ -214,10 +420,41 @@ impl Game {
         &self.player.info
     }
 
+    /// The champions banned by both teams.
+    pub fn bans(&self) -> impl Iterator<Item = core::Champion> + '_ {
+        self.teams
+            .iter()
+            .flat_map(|team| team.bans.iter().map(|ban| ban.champion))
+    }
+
     pub fn update(&mut self, message: Message) -> Option<Event> {
         match message {
-            Message::ExpandPressed => self.is_expanded = !self.is_expanded,
+            Message::ExpandPressed => {
+                self.is_expanded = !self.is_expanded;
+
+                // Arena timelines are not on the rift, there is nothing to chart
+                if self.is_expanded
+                    && self.subteams.is_empty()
+                    && matches!(self.timeline, Timeline::Unrequested)
+                {
+                    self.timeline = Timeline::Loading;
+
+                    return Some(Event::TimelineRequested(self.id.clone()));
+                }
+            }
             Message::NamePressed(riot_id) => return Some(Event::NamePressed(riot_id)),
+            Message::TabSelected(tab) => self.tab = tab,
+            Message::Minimap(message) => {
+                if let Timeline::Loaded { minimap, .. } = &mut self.timeline {
+                    minimap.update(message);
+                }
+            }
+            Message::Builds(message) => {
+                if let Timeline::Loaded { builds, .. } = &mut self.timeline {
+                    builds.update(message);
+                }
+            }
+            Message::Stats(message) => self.stats.update(message),
         }
 
         None
@@ -227,13 +464,31 @@ impl Game {
         let now = time::OffsetDateTime::now_utc();
 
         let match_stats = {
-            // TODO: track and display points gained/lost
-            // let points_icon: Element<Message> = small_icon().into();
-            // let result_points = row![points_icon, text("31 LP").size(16)]
-            //     .spacing(2)
-            //     .align_items(Alignment::Center);
+            let points: Element<'_, _> = if let Some(delta) = self.points {
+                text(formatting::points(delta))
+                    .size(10)
+                    .style(move |theme| text::Style {
+                        color: Some(theme::win_color(theme, delta.points >= 0)),
+                    })
+                    .into()
+            } else {
+                Space::new(0, 0).into()
+            };
+
+            let (title, result) = match &self.player.info.arena {
+                Some(arena) => (
+                    formatting::placement(arena.placement),
+                    game::Result::from(arena.placement <= 4),
+                ),
+                None => (formatting::win(self.result), self.result),
+            };
 
-            let role: Element<'_, _> = if let Some(role) = self.player.info.role {
+            let role: Element<'_, _> = if let Some(arena) = &self.player.info.arena {
+                text!("{} augments", arena.augments.len())
+                    .style(theme::text)
+                    .size(10)
+                    .into()
+            } else if let Some(role) = self.player.info.role {
                 row![
                     icon::role(role).width(12.0).height(12.0),
                     text(formatting::role(role)).style(theme::text).size(10),
@@ -246,14 +501,18 @@ impl Game {
             };
 
             column![
-                text(formatting::win(self.result))
+                text(title)
                     .font(theme::BOLD)
                     .style(move |theme| text::Style {
-                        color: Some(theme::win_color(theme, self.result))
+                        color: Some(theme::win_color(theme, result))
                     })
                     .size(18),
+                points,
                 column![
-                    text(self.queue.to_string()).size(11),
+                    row![text(self.queue.to_string()).size(11)]
+                        .extend(self.lobby.as_ref().map(lobby))
+                        .align_y(Alignment::Center)
+                        .spacing(4),
                     container(
                         text(formatting::time_since(now, self.time))
                             .style(theme::text)
@@ -412,22 +671,59 @@ impl Game {
                 .into()
         };
 
-        let blue_team = self
-            .teams
-            .first()
-            .map(|team| team.players.iter().map(player_name_view))
-            .unwrap();
-        let red_team = self
-            .teams
-            .last()
-            .map(|team| team.players.iter().map(player_name_view))
-            .unwrap();
+        let teams: Element<'_, _> = if self.subteams.is_empty() {
+            let blue_team = self
+                .teams
+                .first()
+                .map(|team| team.players.iter().map(player_name_view))
+                .unwrap();
+            let red_team = self
+                .teams
+                .last()
+                .map(|team| team.players.iter().map(player_name_view))
+                .unwrap();
 
-        let teams = row![
-            column(blue_team).spacing(2).width(Length::Fill),
-            column(red_team).spacing(2).width(Length::Fill),
-        ]
-        .spacing(8);
+            row![
+                column(blue_team).spacing(2).width(Length::Fill),
+                column(red_team).spacing(2).width(Length::Fill),
+            ]
+            .spacing(8)
+            .into()
+        } else {
+            let subteam_view = |subteam: &Subteam| {
+                let champions = subteam.players.iter().map(|player| {
+                    image(player.assets.champion_image.clone())
+                        .width(14.0)
+                        .height(14.0)
+                        .into()
+                });
+
+                row![
+                    text(subteam.placement.to_string())
+                        .size(10)
+                        .style(theme::text)
+                        .width(10)
+                ]
+                .extend(champions)
+                .spacing(2)
+                .align_y(Alignment::Center)
+                .into()
+            };
+
+            // Two columns of four subteams, by placement
+            let (top, bottom) = self.subteams.split_at(self.subteams.len().div_ceil(2));
+
+            row![
+                column(top.iter().map(subteam_view))
+                    .spacing(2)
+                    .width(Length::Fill),
+                column(bottom.iter().map(subteam_view))
+                    .spacing(2)
+                    .width(Length::Fill),
+            ]
+            .spacing(8)
+            .into()
+        };
 
         let chevron_icon = if self.is_expanded {
             icon::chevron_up()
@@ -468,7 +764,13 @@ impl Game {
 
         let overview = container(row![match_info, expand_button.padding(0),]).max_height(100.0);
 
-        let game = if self.is_expanded {
+        let game = if self.is_expanded && !self.subteams.is_empty() {
+            container(row![
+                widget::left_border(self.result),
+                column![overview.height(Length::Shrink), self.subteams()]
+            ])
+            .max_height(640.0)
+        } else if self.is_expanded {
             let max_damage_dealt = self
                 .teams
                 .iter()
@@ -495,13 +797,35 @@ impl Game {
                     )
                 });
 
-            let match_details = container(column(teams));
+            let tabs = row(Tab::ALL.into_iter().map(|tab| {
+                button(text(tab.to_string()).size(12))
+                    .style(move |theme, status| theme::queue_filter(theme, status, self.tab == tab))
+                    .on_press(Message::TabSelected(tab))
+                    .into()
+            }))
+            .spacing(4)
+            .padding(4);
+
+            let match_details: Element<'_, _> = match self.tab {
+                Tab::Overview => container(column(teams)).into(),
+                Tab::Timeline | Tab::Builds => self.timeline(),
+                Tab::Stats => container(
+                    self.stats
+                        .view(
+                            self.teams.iter().flat_map(|team| &team.players),
+                            &self.player.info.puuid,
+                        )
+                        .map(Message::Stats),
+                )
+                .padding(8)
+                .into(),
+            };
 
             container(row![
                 widget::left_border(self.result),
-                column![overview.height(Length::Shrink), match_details,]
+                column![overview.height(Length::Shrink), tabs, match_details]
             ])
-            .max_height(600.0)
+            .max_height(640.0)
         } else {
             container(row![
                 widget::left_border(self.result).max_height(100.0),
@@ -516,6 +840,109 @@ impl Game {
             .style(theme::dark)
             .into()
     }
+
+    fn subteams(&self) -> Element<'_, Message> {
+        let max_damage_dealt = self
+            .subteams
+            .iter()
+            .flat_map(|subteam| &subteam.players)
+            .map(|player| player.info.stats.damage_dealt)
+            .max()
+            .unwrap_or_default()
+            .max(1);
+
+        let subteam_view = |subteam: &Subteam| {
+            let players = subteam.players.iter().map(|player| {
+                let is_player = player.info.puuid == self.player.info.puuid;
+                let stats = player.info.stats;
+                let augments = player
+                    .info
+                    .arena
+                    .as_ref()
+                    .map_or(0, |arena| arena.augments.len());
+
+                container(
+                    row![
+                        image(player.assets.champion_image.clone())
+                            .width(20.0)
+                            .height(20.0),
+                        container(player_name(&player.info.riot_id, 11, is_player))
+                            .width(Length::FillPortion(3)),
+                        container(smaller_text(format!(
+                            "{} / {} / {}",
+                            stats.kills, stats.deaths, stats.assists
+                        )))
+                        .width(Length::FillPortion(2)),
+                        column![
+                            smaller_text(stats.damage_dealt),
+                            container(
+                                progress_bar(
+                                    0.0..=max_damage_dealt as f32,
+                                    stats.damage_dealt as f32
+                                )
+                                .style(|theme| theme::fill_bar(theme, theme.palette().danger)),
+                            )
+                            .width(48.0)
+                            .height(6.0),
+                        ]
+                        .width(Length::FillPortion(2)),
+                        container(smaller_text(format!("{augments} augments")))
+                            .width(Length::FillPortion(2)),
+                    ]
+                    .spacing(8)
+                    .align_y(Alignment::Center),
+                )
+                .padding([2, 4])
+                .style(move |theme| theme::team_player(theme, is_player))
+                .into()
+            });
+
+            let is_top = subteam.placement <= 4;
+
+            column![
+                container(
+                    text(formatting::placement(subteam.placement))
+                        .font(theme::BOLD)
+                        .size(12)
+                        .style(move |theme| text::Style {
+                            color: Some(theme::win_color(theme, is_top))
+                        })
+                )
+                .padding([2, 4])
+                .style(theme::team_header)
+                .width(Length::Fill)
+            ]
+            .extend(players)
+            .into()
+        };
+
+        container(column(self.subteams.iter().map(subteam_view)).spacing(4))
+            .padding(8)
+            .into()
+    }
+
+    fn timeline(&self) -> Element<'_, Message> {
+        let content = match &self.timeline {
+            Timeline::Unrequested => return Space::new(0, 0).into(),
+            Timeline::Loading => smaller_text("Loading timeline..."),
+            Timeline::Loaded { builds, .. } if self.tab == Tab::Builds => {
+                builds.view().map(Message::Builds)
+            }
+            Timeline::Loaded { chart, minimap, .. } => {
+                // Only the rift has a minimap drawn for it
+                if self.queue.map() == game::MapKind::SummonersRift {
+                    column![chart.view(), minimap.view().map(Message::Minimap)]
+                        .spacing(8)
+                        .into()
+                } else {
+                    chart.view()
+                }
+            }
+            Timeline::Unavailable => smaller_text("Timeline unavailable"),
+        };
+
+        container(content).padding(8).center_x(Length::Fill).into()
+    }
 }
 
 fn team<'a>(
@@ -563,6 +990,8 @@ fn team<'a>(
     ];
 
     let total_team_kills = team.players.iter().map(|p| p.info.stats.kills).sum();
+    let team_objectives = team.objectives;
+    let bans = &team.bans;
 
     let team = team
         .players
@@ -585,13 +1014,69 @@ fn team<'a>(
                 .collect()
         });
 
-    let content = row(team.into_iter().map(Element::from));
+    let content = column![
+        container(
+            row![objectives(&team_objectives), bans_row(bans)]
+                .spacing(12)
+                .align_y(Alignment::Center)
+        )
+        .padding([2, 4])
+        .style(theme::team_header),
+        row(team.into_iter().map(Element::from))
+    ];
 
     container(content)
         .style(|theme| theme::team_player(theme, false))
         .into()
 }
 
+fn objectives<'a>(objectives: &game::team::Objectives) -> Element<'a, Message> {
+    let objective = |name: &'static str, objective: game::team::Objective| {
+        // The team that took the first one is highlighted
+        text!("{name} {}", objective.kills)
+            .size(11)
+            .font(if objective.first {
+                theme::BOLD
+            } else {
+                theme::NOTO_SANS
+            })
+            .style(theme::text)
+            .into()
+    };
+
+    row([
+        objective("Towers", objectives.tower),
+        objective("Inhibitors", objectives.inhibitor),
+        objective("Grubs", objectives.grubs),
+        objective("Heralds", objectives.herald),
+        objective("Dragons", objectives.dragon),
+        objective("Barons", objectives.baron),
+    ])
+    .spacing(12)
+    .width(Length::Fill)
+    .into()
+}
+
+fn bans_row(bans: &[Ban]) -> Element<'_, Message> {
+    let icons = row(bans.iter().map(|ban| {
+        image(ban.icon.clone())
+            .width(16.0)
+            .height(16.0)
+            .opacity(0.4)
+            .into()
+    }))
+    .spacing(2);
+
+    if bans.is_empty() {
+        return Space::new(0, 0).into();
+    }
+
+    row![smaller_text("Bans"), icons]
+        .spacing(4)
+        .align_y(Alignment::Center)
+        .into()
+}
+
 fn small_item<'a>(item: Option<image::Handle>) -> Element<'a, Message> {
     match item {
         Some(handle) => image(handle).width(20.0).height(20.0).into(),
@@ -659,6 +1144,23 @@ fn player_name<'a>(riot_id: &account::RiotId, size: u32, is_player: bool) -> Ele
     tooltip(content, overlay, tooltip::Position::Top).into()
 }
 
+fn lobby(lobby: &Lobby) -> Element<'_, Message> {
+    let overlay = container(
+        text!("Average rank: {}", lobby.tier)
+            .style(theme::text)
+            .size(11),
+    )
+    .padding(4)
+    .style(container::dark);
+
+    tooltip(
+        image(lobby.emblem.clone()).width(14.0).height(14.0),
+        overlay,
+        tooltip::Position::Top,
+    )
+    .into()
+}
+
 fn player<'a>(
     player: &'a Player,
     summoner: &'a Player,
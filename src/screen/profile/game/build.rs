@@ -0,0 +1,170 @@
+//! The item build path and skill order of every player of a game.
+use super::Participant;
+use crate::core;
+use crate::core::game::timeline::{self, Skill};
+use crate::theme;
+
+use iced::widget::{Space, button, column, container, image, row, text};
+use iced::{Alignment, Element};
+use itertools::Itertools;
+
+const SKILLS: [Skill; 4] = [Skill::Q, Skill::W, Skill::E, Skill::R];
+const MAX_LEVEL: usize = 18;
+
+#[derive(Debug, Clone)]
+struct Item {
+    icon: image::Handle,
+    is_sold: bool,
+}
+
+#[derive(Debug, Clone)]
+struct Build {
+    /// The items bought, grouped by the minute they were bought at.
+    items: Vec<(u32, Vec<Item>)>,
+    skills: Vec<Skill>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Builds {
+    builds: Vec<Build>,
+    participants: Vec<Participant>,
+    /// The participant whose build is shown, none until one is picked.
+    selected: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    PlayerPressed(usize),
+}
+
+impl Builds {
+    /// Shows the build of the `selected` participant by default, if any.
+    pub fn new(
+        assets: &crate::Assets,
+        timeline: &core::game::Timeline,
+        participants: &[Participant],
+        selected: Option<usize>,
+    ) -> Self {
+        let builds = (1..=participants.len())
+            .map(|id| {
+                let build = timeline.build(timeline::Participant(id as u8));
+
+                let items = build
+                    .items
+                    .iter()
+                    .chunk_by(|purchase| purchase.timestamp / 60_000)
+                    .into_iter()
+                    .map(|(minute, purchases)| {
+                        let items = purchases
+                            .map(|purchase| Item {
+                                icon: assets.item(&purchase.item),
+                                is_sold: purchase.sold_at.is_some(),
+                            })
+                            .collect();
+
+                        (minute, items)
+                    })
+                    .collect();
+
+                Build {
+                    items,
+                    skills: build.skills,
+                }
+            })
+            .collect();
+
+        Self {
+            builds,
+            participants: participants.to_vec(),
+            selected,
+        }
+    }
+
+    pub fn update(&mut self, message: Message) {
+        match message {
+            Message::PlayerPressed(index) => self.selected = Some(index),
+        }
+    }
+
+    /// Keeps the player selected before the builds were rebuilt.
+    pub fn restore(&mut self, previous: &Builds) {
+        self.selected = previous.selected;
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let players = row(self
+            .participants
+            .iter()
+            .enumerate()
+            .map(|(index, participant)| {
+                let selected = self.selected == Some(index);
+
+                button(image(participant.champion.clone()).width(20.0).height(20.0))
+                    .padding(2)
+                    .style(move |theme, status| theme::queue_filter(theme, status, selected))
+                    .on_press(Message::PlayerPressed(index))
+                    .into()
+            }))
+        .spacing(2);
+
+        let Some(build) = self.selected.and_then(|selected| self.builds.get(selected)) else {
+            return players.into();
+        };
+
+        let items = row(build.items.iter().map(|(minute, items)| {
+            let icons = row(items.iter().map(|item| {
+                image(item.icon.clone())
+                    .width(24.0)
+                    .height(24.0)
+                    .opacity(if item.is_sold { 0.4 } else { 1.0 })
+                    .into()
+            }))
+            .spacing(2);
+
+            column![icons, text!("{minute} min").size(10).style(theme::text)]
+                .spacing(2)
+                .align_x(Alignment::Center)
+                .into()
+        }))
+        .spacing(8)
+        .wrap();
+
+        let skills = column(SKILLS.into_iter().map(|skill| {
+            let cells = (0..MAX_LEVEL).map(|level| {
+                let is_leveled = build.skills.get(level) == Some(&skill);
+                let content: Element<'_, Message> = if is_leveled {
+                    text(level + 1).size(10).font(theme::BOLD).into()
+                } else {
+                    Space::new(0, 0).into()
+                };
+
+                container(content)
+                    .center(16)
+                    .style(move |theme| theme::team_player(theme, is_leveled))
+                    .into()
+            });
+
+            row![
+                text(format!("{skill:?}"))
+                    .size(11)
+                    .font(theme::BOLD)
+                    .width(16)
+            ]
+            .extend(cells)
+            .spacing(2)
+            .align_y(Alignment::Center)
+            .into()
+        }))
+        .spacing(2);
+
+        column![
+            players,
+            text("Build path").size(12).font(theme::BOLD),
+            items,
+            text("Skill order").size(12).font(theme::BOLD),
+            skills,
+        ]
+        .spacing(8)
+        .into()
+    }
+}
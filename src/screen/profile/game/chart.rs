@@ -0,0 +1,294 @@
+//! A chart of the gold and experience difference between both teams over a game.
+use super::Participant;
+use crate::core;
+use crate::formatting;
+
+use iced::mouse;
+use iced::widget::canvas::{self, Frame, Geometry, Path, Stroke, Text};
+use iced::{Color, Element, Length, Pixels, Point, Rectangle, Renderer, Size, Theme};
+
+/// Space on the left of the plot, for the scale labels.
+const LEFT: f32 = 40.0;
+const PADDING: f32 = 8.0;
+const TOOLTIP: Size = Size::new(84.0, 48.0);
+
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+    minute: f32,
+    gold: i64,
+    xp: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct Chart {
+    samples: Vec<Sample>,
+    /// The largest difference shown, at the top and bottom of the plot.
+    range: i64,
+}
+
+impl Chart {
+    /// Computes the differences in favor of `team`.
+    pub fn new(
+        timeline: &core::game::Timeline,
+        participants: &[Participant],
+        team: core::Team,
+    ) -> Self {
+        let samples = timeline
+            .frames
+            .iter()
+            .map(|frame| {
+                let (gold, xp) = frame.players.iter().zip(participants).fold(
+                    (0, 0),
+                    |(gold, xp), (stats, participant)| {
+                        let sign = if participant.team == team { 1 } else { -1 };
+
+                        (gold + sign * stats.gold as i64, xp + sign * stats.xp as i64)
+                    },
+                );
+
+                Sample {
+                    minute: frame.timestamp as f32 / 60_000.0,
+                    gold,
+                    xp,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // Both differences share a scale so they can be compared at a glance
+        let range = samples
+            .iter()
+            .map(|sample| sample.gold.abs().max(sample.xp.abs()))
+            .max()
+            .unwrap_or_default()
+            .max(1000);
+
+        Self { samples, range }
+    }
+
+    pub fn view<'a, Message: 'a>(&'a self) -> Element<'a, Message> {
+        iced::widget::canvas(self)
+            .width(Length::Fill)
+            .height(140)
+            .into()
+    }
+
+    fn plot(bounds: Size) -> Rectangle {
+        Rectangle {
+            x: LEFT,
+            y: PADDING,
+            width: (bounds.width - LEFT - PADDING).max(0.0),
+            height: (bounds.height - PADDING * 2.0).max(0.0),
+        }
+    }
+
+    fn duration(&self) -> f32 {
+        self.samples
+            .last()
+            .map(|sample| sample.minute)
+            .unwrap_or_default()
+            .max(1.0)
+    }
+
+    /// The sample closest to `x`, relative to the canvas.
+    fn sample_at(&self, x: f32, bounds: Size) -> Option<usize> {
+        let plot = Self::plot(bounds);
+        let minute = (x - plot.x) / plot.width * self.duration();
+
+        self.samples
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| {
+                (a.minute - minute)
+                    .abs()
+                    .total_cmp(&(b.minute - minute).abs())
+            })
+            .map(|(index, _)| index)
+    }
+}
+
+impl<Message> canvas::Program<Message> for Chart {
+    /// The hovered sample.
+    type State = Option<usize>;
+
+    fn update(
+        &self,
+        hovered: &mut Self::State,
+        event: &canvas::Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Option<canvas::Action<Message>> {
+        let canvas::Event::Mouse(_) = event else {
+            return None;
+        };
+
+        let sample = cursor
+            .position_in(bounds)
+            .and_then(|position| self.sample_at(position.x, bounds.size()));
+
+        if *hovered == sample {
+            return None;
+        }
+
+        *hovered = sample;
+
+        Some(canvas::Action::request_redraw())
+    }
+
+    fn draw(
+        &self,
+        hovered: &Self::State,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let palette = theme.extended_palette();
+        let text_color = palette.background.base.text.scale_alpha(0.8);
+        let gold_color = palette.warning.base.color;
+        let xp_color = palette.primary.base.color;
+
+        let mut frame = Frame::new(renderer, bounds.size());
+        let plot = Self::plot(bounds.size());
+        let duration = self.duration();
+
+        let x = |minute: f32| plot.x + minute / duration * plot.width;
+        let y = |value: i64| plot.center_y() - value as f32 / self.range as f32 * plot.height / 2.0;
+
+        let label = |frame: &mut Frame, content: String, position: Point, color: Color| {
+            frame.fill_text(Text {
+                content,
+                position,
+                color,
+                size: Pixels(10.0),
+                ..Text::default()
+            });
+        };
+
+        // Scale
+        frame.stroke(
+            &Path::line(
+                Point::new(plot.x, plot.center_y()),
+                Point::new(plot.x + plot.width, plot.center_y()),
+            ),
+            Stroke::default()
+                .with_color(palette.background.strong.color)
+                .with_width(1.0),
+        );
+        label(
+            &mut frame,
+            formatting::difference(self.range),
+            Point::new(PADDING / 2.0, plot.y),
+            text_color,
+        );
+        label(
+            &mut frame,
+            String::from("0"),
+            Point::new(PADDING / 2.0, plot.center_y() - 6.0),
+            text_color,
+        );
+        label(
+            &mut frame,
+            formatting::difference(-self.range),
+            Point::new(PADDING / 2.0, plot.y + plot.height - 12.0),
+            text_color,
+        );
+
+        // Legend
+        label(
+            &mut frame,
+            String::from("Gold"),
+            Point::new(plot.x + 4.0, plot.y),
+            gold_color,
+        );
+        label(
+            &mut frame,
+            String::from("XP"),
+            Point::new(plot.x + 36.0, plot.y),
+            xp_color,
+        );
+
+        let series: [(fn(&Sample) -> i64, Color); 2] = [
+            (|sample| sample.gold, gold_color),
+            (|sample| sample.xp, xp_color),
+        ];
+
+        for (value, color) in series {
+            let line = Path::new(|builder| {
+                for (index, sample) in self.samples.iter().enumerate() {
+                    let point = Point::new(x(sample.minute), y(value(sample)));
+
+                    if index == 0 {
+                        builder.move_to(point);
+                    } else {
+                        builder.line_to(point);
+                    }
+                }
+            });
+
+            frame.stroke(&line, Stroke::default().with_color(color).with_width(2.0));
+        }
+
+        if let Some(sample) = hovered.and_then(|index| self.samples.get(index)) {
+            let at = x(sample.minute);
+
+            frame.stroke(
+                &Path::line(Point::new(at, plot.y), Point::new(at, plot.y + plot.height)),
+                Stroke::default().with_color(text_color).with_width(1.0),
+            );
+
+            for (value, color) in series {
+                frame.fill(&Path::circle(Point::new(at, y(value(sample))), 3.0), color);
+            }
+
+            // Keep the tooltip inside the plot, on the left of the line near the end
+            let left = if at + TOOLTIP.width + PADDING > plot.x + plot.width {
+                at - TOOLTIP.width - PADDING
+            } else {
+                at + PADDING
+            };
+            let tooltip = Path::rectangle(Point::new(left, plot.y), TOOLTIP);
+
+            frame.fill(&tooltip, palette.background.base.color);
+            frame.stroke(
+                &tooltip,
+                Stroke::default()
+                    .with_color(palette.background.strong.color)
+                    .with_width(1.0),
+            );
+
+            label(
+                &mut frame,
+                format!("{} min", sample.minute.round()),
+                Point::new(left + 6.0, plot.y + 4.0),
+                text_color,
+            );
+            label(
+                &mut frame,
+                format!("Gold {}", formatting::difference(sample.gold)),
+                Point::new(left + 6.0, plot.y + 18.0),
+                gold_color,
+            );
+            label(
+                &mut frame,
+                format!("XP {}", formatting::difference(sample.xp)),
+                Point::new(left + 6.0, plot.y + 32.0),
+                xp_color,
+            );
+        }
+
+        vec![frame.into_geometry()]
+    }
+
+    fn mouse_interaction(
+        &self,
+        _hovered: &Self::State,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> mouse::Interaction {
+        if cursor.is_over(bounds) {
+            mouse::Interaction::Crosshair
+        } else {
+            mouse::Interaction::default()
+        }
+    }
+}
@@ -0,0 +1,283 @@
+//! A minimap of where kills, ward placements and objectives happened during a game.
+use super::Participant;
+use crate::core;
+use crate::core::game::timeline::{self, Event};
+use crate::theme;
+use crate::theme::icon;
+
+use iced::mouse;
+use iced::widget::canvas::{self, Frame, Geometry, Path, Stroke};
+use iced::widget::{button, column, container, image, row, slider, stack, text};
+use iced::{Alignment, Element, Point, Rectangle, Renderer, Size, Theme};
+
+/// The size of the minimap, in logical pixels.
+const SIZE: f32 = 256.0;
+/// The size of Summoner's Rift, in game units.
+const RIFT: f32 = 15_000.0;
+
+/// Drawn in this order, so wards don't hide the rest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Kind {
+    Ward,
+    Kill,
+    Objective,
+}
+
+#[derive(Debug, Clone)]
+struct Marker {
+    kind: Kind,
+    /// Minutes since the game started.
+    minute: u32,
+    /// Whether the team of the player is credited for it.
+    is_ally: bool,
+    /// The index of every participant involved.
+    involved: Vec<usize>,
+    position: timeline::Position,
+}
+
+#[derive(Debug, Clone)]
+pub struct Minimap {
+    markers: Vec<Marker>,
+    participants: Vec<Participant>,
+    /// The length of the game, in minutes.
+    duration: u32,
+
+    player: Option<usize>,
+    start: u32,
+    end: u32,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    PlayerPressed(usize),
+    StartChanged(u32),
+    EndChanged(u32),
+}
+
+impl Minimap {
+    pub fn new(
+        timeline: &core::game::Timeline,
+        participants: &[Participant],
+        team: core::Team,
+    ) -> Self {
+        let team_of = |participant: &timeline::Participant| {
+            participants
+                .get(participant.index())
+                .map(|participant| participant.team)
+        };
+
+        let mut markers = timeline
+            .events
+            .iter()
+            .filter_map(|timed| {
+                let (kind, is_ally, involved, position) = match &timed.event {
+                    Event::Kill {
+                        killer,
+                        victim,
+                        assists,
+                        position,
+                    } => {
+                        let involved = killer
+                            .iter()
+                            .chain(assists)
+                            .chain([victim])
+                            .map(timeline::Participant::index)
+                            .collect();
+
+                        (Kind::Kill, team_of(victim)? != team, involved, *position)
+                    }
+                    Event::Ward {
+                        participant,
+                        placed: true,
+                        ..
+                    } => (
+                        Kind::Ward,
+                        team_of(participant)? == team,
+                        vec![participant.index()],
+                        timeline.position(*participant, timed.timestamp)?,
+                    ),
+                    Event::Objective {
+                        killer,
+                        team: taker,
+                        position: Some(position),
+                        ..
+                    } => (
+                        Kind::Objective,
+                        *taker == team,
+                        killer.iter().map(timeline::Participant::index).collect(),
+                        *position,
+                    ),
+                    _ => return None,
+                };
+
+                Some(Marker {
+                    kind,
+                    minute: timed.timestamp / 60_000,
+                    is_ally,
+                    involved,
+                    position,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        markers.sort_by_key(|marker| marker.kind);
+
+        let duration = timeline
+            .frames
+            .last()
+            .map(|frame| frame.timestamp / 60_000 + 1)
+            .unwrap_or_default();
+
+        Self {
+            markers,
+            participants: participants.to_vec(),
+            duration,
+            player: None,
+            start: 0,
+            end: duration,
+        }
+    }
+
+    pub fn update(&mut self, message: Message) {
+        match message {
+            Message::PlayerPressed(index) => {
+                self.player = if self.player == Some(index) {
+                    None
+                } else {
+                    Some(index)
+                };
+            }
+            Message::StartChanged(start) => self.start = start.min(self.end),
+            Message::EndChanged(end) => self.end = end.max(self.start),
+        }
+    }
+
+    /// Keeps the filters of the minimap of the same game before it was rebuilt.
+    pub fn restore(&mut self, previous: &Minimap) {
+        self.player = previous.player;
+        self.start = previous.start;
+        self.end = previous.end;
+    }
+
+    fn is_visible(&self, marker: &Marker) -> bool {
+        (self.start..=self.end).contains(&marker.minute)
+            && self
+                .player
+                .is_none_or(|player| marker.involved.contains(&player))
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let map = container(stack![
+            icon::summoners_rift().width(SIZE).height(SIZE),
+            iced::widget::canvas(self).width(SIZE).height(SIZE),
+        ])
+        .style(theme::team_header);
+
+        let players = row(self
+            .participants
+            .iter()
+            .enumerate()
+            .map(|(index, participant)| {
+                let selected = self.player == Some(index);
+
+                button(image(participant.champion.clone()).width(20.0).height(20.0))
+                    .padding(2)
+                    .style(move |theme, status| theme::queue_filter(theme, status, selected))
+                    .on_press(Message::PlayerPressed(index))
+                    .into()
+            }))
+        .spacing(2);
+
+        let range = |label: &'static str, value: u32, on_change: fn(u32) -> Message| {
+            row![
+                text(label).size(11).style(theme::text).width(32),
+                slider(0..=self.duration, value, on_change),
+                text!("{value}m").size(11).style(theme::text).width(32),
+            ]
+            .spacing(8)
+            .align_y(Alignment::Center)
+        };
+
+        let legend = column![
+            text("Circles are kills, squares wards and diamonds objectives")
+                .size(11)
+                .style(theme::text),
+            row![
+                text("Your team").size(11).style(theme::victory),
+                text("Enemy team").size(11).style(theme::defeat),
+            ]
+            .spacing(8),
+        ]
+        .spacing(4);
+
+        row![
+            map,
+            column![
+                players,
+                range("From", self.start, Message::StartChanged),
+                range("To", self.end, Message::EndChanged),
+                legend,
+            ]
+            .spacing(8),
+        ]
+        .spacing(12)
+        .into()
+    }
+}
+
+impl<T> canvas::Program<T> for Minimap {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let palette = theme.extended_palette();
+        let mut frame = Frame::new(renderer, bounds.size());
+
+        // The origin of the rift is its bottom left corner
+        let point = |position: timeline::Position| {
+            Point::new(
+                position.x as f32 / RIFT * bounds.width,
+                (1.0 - position.y as f32 / RIFT) * bounds.height,
+            )
+        };
+
+        for marker in self.markers.iter().filter(|marker| self.is_visible(marker)) {
+            let center = point(marker.position);
+            let shape = match marker.kind {
+                Kind::Kill => Path::circle(center, 4.0),
+                Kind::Ward => Path::rectangle(
+                    Point::new(center.x - 2.5, center.y - 2.5),
+                    Size::new(5.0, 5.0),
+                ),
+                Kind::Objective => Path::new(|builder| {
+                    builder.move_to(Point::new(center.x, center.y - 6.0));
+                    builder.line_to(Point::new(center.x + 6.0, center.y));
+                    builder.line_to(Point::new(center.x, center.y + 6.0));
+                    builder.line_to(Point::new(center.x - 6.0, center.y));
+                    builder.close();
+                }),
+            };
+            let color = if marker.is_ally {
+                palette.success.base.color
+            } else {
+                palette.danger.base.color
+            };
+
+            frame.fill(&shape, color.scale_alpha(0.85));
+            frame.stroke(
+                &shape,
+                Stroke::default()
+                    .with_color(palette.background.base.color)
+                    .with_width(1.0),
+            );
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
@@ -0,0 +1,269 @@
+//! A table of the stats of every player of a game, sortable by any column.
+use super::Player;
+use crate::core::game::player::{FirstBlood, Stats};
+use crate::theme;
+use crate::theme::icon;
+
+use iced::widget::{button, column, container, image, row, text};
+use iced::{Alignment, Element, Length};
+use itertools::Itertools;
+
+struct Column {
+    name: &'static str,
+    value: fn(&Stats) -> u32,
+    format: fn(u32) -> String,
+}
+
+impl Column {
+    const fn new(name: &'static str, value: fn(&Stats) -> u32) -> Self {
+        Self {
+            name,
+            value,
+            format: number,
+        }
+    }
+
+    const fn seconds(name: &'static str, value: fn(&Stats) -> u32) -> Self {
+        Self {
+            name,
+            value,
+            format: seconds,
+        }
+    }
+}
+
+struct Category {
+    name: &'static str,
+    columns: &'static [Column],
+}
+
+const CATEGORIES: &[Category] = &[
+    Category {
+        name: "Combat",
+        columns: &[
+            Column::new("Kills", |stats| stats.kills),
+            Column::new("Deaths", |stats| stats.deaths),
+            Column::new("Assists", |stats| stats.assists),
+            Column {
+                name: "First blood",
+                value: |stats| stats.first_blood as u32,
+                format: first_blood,
+            },
+            Column::new("Spree", |stats| stats.largest_killing_spree),
+            Column::new("Double", |stats| stats.double_kills),
+            Column::new("Triple", |stats| stats.triple_kills),
+            Column::new("Quadra", |stats| stats.quadra_kills),
+            Column::new("Penta", |stats| stats.penta_kills),
+        ],
+    },
+    Category {
+        name: "Damage",
+        columns: &[
+            Column::new("Champions", |stats| stats.damage_dealt),
+            Column::new("Physical", |stats| stats.physical_damage_dealt),
+            Column::new("Magic", |stats| stats.magic_damage_dealt),
+            Column::new("True", |stats| stats.true_damage_dealt),
+            Column::new("Objectives", |stats| stats.objective_damage),
+            Column::new("Taken", |stats| stats.damage_taken),
+            Column::new("Mitigated", |stats| stats.damage_mitigated),
+        ],
+    },
+    Category {
+        name: "Utility",
+        columns: &[
+            Column::new("Healing", |stats| stats.healing),
+            Column::new("Ally heals", |stats| stats.ally_healing),
+            Column::new("Shielding", |stats| stats.ally_shielding),
+            Column::seconds("CC", |stats| stats.crowd_control),
+            Column::seconds("Time dead", |stats| stats.time_dead),
+        ],
+    },
+    Category {
+        name: "Economy",
+        columns: &[
+            Column::new("Level", |stats| stats.level),
+            Column::new("Gold", |stats| stats.gold),
+            Column::new("Spent", |stats| stats.gold_spent),
+            Column::new("CS", |stats| stats.creep_score),
+            Column::new("Monsters", |stats| stats.monster_score),
+            Column::new("Turrets", |stats| stats.turret_kills),
+        ],
+    },
+    Category {
+        name: "Vision",
+        columns: &[
+            Column::new("Score", |stats| stats.vision_score),
+            Column::new("Placed", |stats| stats.wards_placed),
+            Column::new("Removed", |stats| stats.wards_removed),
+            Column::new("Control", |stats| stats.control_wards),
+        ],
+    },
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Order {
+    Descending,
+    Ascending,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Table {
+    category: usize,
+    /// The column sorted by, players are in team order otherwise.
+    sort: Option<(usize, Order)>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    CategorySelected(usize),
+    SortPressed(usize),
+}
+
+impl Table {
+    pub fn update(&mut self, message: Message) {
+        match message {
+            Message::CategorySelected(category) => {
+                self.category = category;
+                self.sort = None;
+            }
+            Message::SortPressed(column) => {
+                self.sort = match self.sort {
+                    Some((sorted, Order::Descending)) if sorted == column => {
+                        Some((column, Order::Ascending))
+                    }
+                    Some((sorted, Order::Ascending)) if sorted == column => None,
+                    _ => Some((column, Order::Descending)),
+                };
+            }
+        }
+    }
+
+    pub fn view<'a>(
+        &self,
+        players: impl Iterator<Item = &'a Player>,
+        puuid: &str,
+    ) -> Element<'a, Message> {
+        let category = &CATEGORIES[self.category.min(CATEGORIES.len() - 1)];
+
+        let categories = row(CATEGORIES.iter().enumerate().map(|(index, category)| {
+            let selected = self.category == index;
+
+            button(text(category.name).size(11))
+                .style(move |theme, status| theme::queue_filter(theme, status, selected))
+                .on_press(Message::CategorySelected(index))
+                .into()
+        }))
+        .spacing(4);
+
+        let header = row![container(text("Player").size(11).font(theme::BOLD)).width(140)]
+            .extend(category.columns.iter().enumerate().map(|(index, column)| {
+                let label =
+                    row![text(column.name).size(11).font(theme::BOLD)]
+                        .extend(self.sort.filter(|(sorted, _)| *sorted == index).map(
+                            |(_, order)| {
+                                match order {
+                                    Order::Descending => icon::chevron_down(),
+                                    Order::Ascending => icon::chevron_up(),
+                                }
+                                .width(10.0)
+                                .height(10.0)
+                                .into()
+                            },
+                        ))
+                        .spacing(2)
+                        .align_y(Alignment::Center);
+
+                button(label)
+                    .style(button::text)
+                    .padding(2)
+                    .width(Length::FillPortion(1))
+                    .on_press(Message::SortPressed(index))
+                    .into()
+            }))
+            .align_y(Alignment::Center)
+            .padding(4);
+
+        let mut players = players.collect_vec();
+
+        if let Some((index, order)) = self.sort {
+            let value = category.columns[index].value;
+
+            players.sort_by_key(|player| value(&player.info.stats));
+
+            if order == Order::Descending {
+                players.reverse();
+            }
+        }
+
+        let rows = players.into_iter().map(|player| {
+            let is_player = player.info.puuid == puuid;
+            let name = player
+                .info
+                .riot_id
+                .name
+                .clone()
+                .unwrap_or(String::from("Unknown"));
+
+            let info = row![
+                image(player.assets.champion_image.clone())
+                    .width(20.0)
+                    .height(20.0),
+                text(if name.chars().count() > 12 {
+                    super::truncated(name, 12)
+                } else {
+                    name
+                })
+                .font(if is_player {
+                    theme::BOLD
+                } else {
+                    theme::NOTO_SANS
+                })
+                .shaping(text::Shaping::Advanced)
+                .size(11),
+            ]
+            .spacing(4)
+            .align_y(Alignment::Center)
+            .width(140);
+
+            let cells = category.columns.iter().map(|column| {
+                let value = (column.value)(&player.info.stats);
+
+                text((column.format)(value))
+                    .size(11)
+                    .style(theme::text)
+                    .width(Length::FillPortion(1))
+                    .into()
+            });
+
+            container(
+                row![info]
+                    .extend(cells)
+                    .align_y(Alignment::Center)
+                    .padding(4),
+            )
+            .style(move |theme| theme::team_player(theme, is_player))
+            .into()
+        });
+
+        column![categories, column![header].extend(rows)]
+            .spacing(8)
+            .into()
+    }
+}
+
+fn number(value: u32) -> String {
+    value.to_string()
+}
+
+fn seconds(value: u32) -> String {
+    format!("{}m {:02}s", value / 60, value % 60)
+}
+
+fn first_blood(value: u32) -> String {
+    match value {
+        value if value == FirstBlood::Kill as u32 => "Kill",
+        value if value == FirstBlood::Assist as u32 => "Assist",
+        _ => "-",
+    }
+    .to_string()
+}
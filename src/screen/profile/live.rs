@@ -0,0 +1,267 @@
+use crate::core::account;
+use crate::core::game::Queue;
+use crate::core::summoner::{League, Ranks, Tier};
+use crate::core::{self, live};
+use crate::formatting;
+use crate::theme;
+
+use iced::widget::{button, column, container, horizontal_space, image, row, text};
+use iced::{Alignment, Element, Length};
+use itertools::Itertools;
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    NamePressed(account::RiotId),
+}
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    NamePressed(account::RiotId),
+}
+
+/// The game the summoner is currently playing.
+#[derive(Debug, Clone, Default)]
+pub enum Live {
+    #[default]
+    Unrequested,
+    Loading,
+    /// The summoner is not in game.
+    Idle,
+    Failed,
+    Playing(Game),
+}
+
+#[derive(Debug, Clone)]
+pub struct Game {
+    game: live::Game,
+    teams: Vec<Team>,
+}
+
+#[derive(Debug, Clone)]
+struct Team {
+    id: core::Team,
+    players: Vec<Player>,
+}
+
+#[derive(Debug, Clone)]
+struct Player {
+    riot_id: account::RiotId,
+    is_summoner: bool,
+    champion: image::Handle,
+    spells: [image::Handle; 2],
+    keystone: Option<image::Handle>,
+    league: Option<Rank>,
+}
+
+#[derive(Debug, Clone)]
+struct Rank {
+    tier: Tier,
+    emblem: image::Handle,
+    wins: u32,
+    losses: u32,
+}
+
+impl Live {
+    pub fn update(&mut self, message: Message) -> Option<Event> {
+        match message {
+            Message::NamePressed(riot_id) => Some(Event::NamePressed(riot_id)),
+        }
+    }
+
+    pub fn is_playing(&self) -> bool {
+        matches!(self, Live::Playing(_))
+    }
+
+    /// Shows the leagues and icons of the players that became known since the game was fetched.
+    pub fn set_ranks(&mut self, assets: &crate::Assets, puuid: &str, ranks: &Ranks) {
+        if let Live::Playing(game) = self {
+            *game = Game::new(assets, puuid, game.game.clone(), ranks);
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let status = |status: &'static str| -> Element<'static, Message> {
+            container(text(status).size(14).style(theme::text))
+                .padding(16)
+                .center_x(680)
+                .into()
+        };
+
+        match self {
+            Live::Unrequested | Live::Loading => status("Looking for a game in progress..."),
+            Live::Idle => status("Not in game right now"),
+            Live::Failed => status("Failed to look for a game in progress"),
+            Live::Playing(game) => game.view(),
+        }
+    }
+}
+
+impl Game {
+    /// The leagues of the players are looked up in the `ranks`.
+    pub fn new(assets: &crate::Assets, puuid: &str, game: live::Game, ranks: &Ranks) -> Self {
+        let teams = game
+            .players
+            .iter()
+            .map(|player| player.team)
+            .unique()
+            .sorted_by_key(|team| *team != core::Team::BLUE)
+            .map(|id| Team {
+                id,
+                players: game
+                    .players
+                    .iter()
+                    .filter(|player| player.team == id)
+                    .map(|player| Player::new(assets, puuid, game.queue, player, ranks))
+                    .collect(),
+            })
+            .collect();
+
+        Self { game, teams }
+    }
+
+    fn view(&self) -> Element<'_, Message> {
+        let now = time::OffsetDateTime::now_utc();
+        let clock = match self.game.elapsed(now) {
+            Some(elapsed) => formatting::clock(elapsed),
+            None => String::from("Loading"),
+        };
+
+        let header = row![
+            text(self.game.queue.to_string()).font(theme::BOLD).size(14),
+            text("·").style(theme::text),
+            text(self.game.queue.map().to_string())
+                .style(theme::text)
+                .size(12),
+            horizontal_space().width(Length::Fill),
+            theme::icon::clock().width(12.0).height(12.0),
+            text(clock).size(12),
+        ]
+        .align_y(Alignment::Center)
+        .spacing(4)
+        .padding(8);
+
+        column![header]
+            .extend(self.teams.iter().map(team))
+            .spacing(4)
+            .width(680)
+            .into()
+    }
+}
+
+impl Player {
+    fn new(
+        assets: &crate::Assets,
+        puuid: &str,
+        queue: Queue,
+        player: &live::Player,
+        ranks: &Ranks,
+    ) -> Self {
+        Self {
+            riot_id: player.riot_id.clone(),
+            is_summoner: player.puuid.as_deref() == Some(puuid),
+            champion: assets.champion(&player.champion),
+            spells: [
+                assets.spell(&player.summoner_spells.first()),
+                assets.spell(&player.summoner_spells.second()),
+            ],
+            keystone: player.keystone.map(|keystone| assets.rune(&keystone)),
+            league: player
+                .puuid
+                .as_deref()
+                .and_then(|puuid| ranks.league(puuid, queue))
+                .and_then(|league| Rank::new(assets, league)),
+        }
+    }
+}
+
+impl Rank {
+    fn new(assets: &crate::Assets, league: &League) -> Option<Self> {
+        let tier = league.tier?;
+
+        Some(Self {
+            tier,
+            emblem: assets.emblem(&tier),
+            wins: league.wins,
+            losses: league.losses,
+        })
+    }
+}
+
+fn team(team: &Team) -> Element<'_, Message> {
+    let header = container(text(formatting::team(team.id)).font(theme::BOLD).size(12))
+        .padding(4)
+        .style(theme::team_header)
+        .width(Length::Fill);
+
+    column![header]
+        .extend(team.players.iter().map(player))
+        .into()
+}
+
+fn player(player: &Player) -> Element<'_, Message> {
+    let icon = |handle: &image::Handle, size: f32| image(handle.clone()).width(size).height(size);
+
+    let spells = column(player.spells.iter().map(|spell| icon(spell, 16.0).into())).spacing(2);
+    let keystone: Element<'_, _> = match &player.keystone {
+        Some(keystone) => icon(keystone, 20.0).into(),
+        None => horizontal_space().width(20).into(),
+    };
+
+    let name = player.riot_id.name.as_deref().unwrap_or("Unknown");
+    let mut name = text(name)
+        .font(theme::NOTO_SANS)
+        .shaping(text::Shaping::Advanced)
+        .size(12);
+
+    if player.is_summoner {
+        name = name.font(theme::BOLD);
+    }
+
+    let name: Element<'_, _> = if player.riot_id.name.is_some() {
+        button(name)
+            .style(button::text)
+            .padding(0)
+            .on_press(Message::NamePressed(player.riot_id.clone()))
+            .into()
+    } else {
+        name.into()
+    };
+
+    let league: Element<'_, _> = match &player.league {
+        Some(rank) => {
+            let games = rank.wins + rank.losses;
+            let win_rate = (rank.wins as f32 / games.max(1) as f32 * 100.0).round();
+
+            row![
+                icon(&rank.emblem, 24.0),
+                column![
+                    text(rank.tier.to_string()).size(12),
+                    text!("{}W {}L · {win_rate:.0}%", rank.wins, rank.losses)
+                        .style(theme::text)
+                        .size(10),
+                ],
+            ]
+            .align_y(Alignment::Center)
+            .spacing(6)
+            .into()
+        }
+        None => text("Unranked").style(theme::text).size(12).into(),
+    };
+
+    let is_summoner = player.is_summoner;
+
+    container(
+        row![
+            icon(&player.champion, 32.0),
+            spells,
+            keystone,
+            container(name).width(Length::FillPortion(3)),
+            container(league).width(Length::FillPortion(2)),
+        ]
+        .align_y(Alignment::Center)
+        .spacing(4),
+    )
+    .padding(4)
+    .style(move |theme| theme::team_player(theme, is_summoner))
+    .into()
+}
@@ -1,4 +1,7 @@
-use aery_core::summoner::league;
+mod chart;
+use chart::Chart;
+
+use aery_core::summoner::{History, league};
 use iced::{
     Alignment, Element, Length, padding,
     widget::{button, column, container, horizontal_space, image, progress_bar, row, text},
@@ -11,18 +14,26 @@ use crate::profile;
 use crate::theme;
 use crate::theme::icon;
 
-fn ranked_container<'a>(
-    queue: game::Queue,
-    tier: Tier,
-    wins: u16,
-    losses: u16,
-    handle: image::Handle,
-) -> Element<'a, Message> {
+fn ranked_container(queue: game::Queue, stats: &Stats, is_expanded: bool) -> Element<'_, Message> {
+    let Stats {
+        tier,
+        wins,
+        losses,
+        ref handle,
+        ref chart,
+    } = *stats;
+
     let left_bar = container(horizontal_space().width(2))
         .style(theme::left_bar)
         .height(18);
 
-    let chevron_down = icon::chevron_down().width(12.0).height(12.0);
+    let chevron = if is_expanded {
+        icon::chevron_up()
+    } else {
+        icon::chevron_down()
+    }
+    .width(12.0)
+    .height(12.0);
 
     let size = match queue {
         game::Queue::RankedSolo => 100.0,
@@ -56,54 +67,61 @@ fn ranked_container<'a>(
 
     let win_rate = ((wins as f32 / (wins + losses) as f32) * 100.0).ceil();
 
-    container(column![
-        row![
-            left_bar,
-            horizontal_space().width(4),
-            text(queue.to_string()).font(theme::BOLD).size(14),
-            horizontal_space().width(Length::Fill),
-            button(chevron_down)
-                .style(theme::expand)
-                .padding(4)
-                .on_press(Message::Expand),
-        ]
-        .padding(padding::all(12).bottom(0))
-        .spacing(2)
-        .align_y(Alignment::Center),
-        row![
-            container(image(handle).width(emblem_size).height(emblem_size))
-                .center_x(size)
-                .center_y(size),
-            column![
-                row![
-                    text(tier).font(theme::BOLD).size(16),
-                    text("·").style(theme::text).size(16),
-                    text(format!("{lp} LP")).style(theme::text).size(12)
-                ]
-                .align_y(Alignment::Center)
-                .spacing(4),
-                row![
-                    text(format!("{wins}W {losses}L"))
-                        .style(theme::text)
-                        .size(12),
-                    text("·").style(theme::text),
-                    text(format!("{win_rate:.0}%"))
-                        .font(theme::BOLD)
-                        .style(theme::victory)
-                        .size(12)
-                ]
-                .align_y(Alignment::Center)
-                .spacing(4),
-                container(progress_bar(0.0..=100.0, win_rate).style(theme::ratio_bar))
-                    .width(120)
-                    .height(4),
+    container(
+        column![
+            row![
+                left_bar,
+                horizontal_space().width(4),
+                text(queue.to_string()).font(theme::BOLD).size(14),
+                horizontal_space().width(Length::Fill),
+                button(chevron)
+                    .style(theme::expand)
+                    .padding(4)
+                    .on_press(Message::Expand(queue)),
             ]
+            .padding(padding::all(12).bottom(0))
             .spacing(2)
+            .align_y(Alignment::Center),
+            row![
+                container(image(handle.clone()).width(emblem_size).height(emblem_size))
+                    .center_x(size)
+                    .center_y(size),
+                column![
+                    row![
+                        text(tier).font(theme::BOLD).size(16),
+                        text("·").style(theme::text).size(16),
+                        text(format!("{lp} LP")).style(theme::text).size(12)
+                    ]
+                    .align_y(Alignment::Center)
+                    .spacing(4),
+                    row![
+                        text(format!("{wins}W {losses}L"))
+                            .style(theme::text)
+                            .size(12),
+                        text("·").style(theme::text),
+                        text(format!("{win_rate:.0}%"))
+                            .font(theme::BOLD)
+                            .style(theme::victory)
+                            .size(12)
+                    ]
+                    .align_y(Alignment::Center)
+                    .spacing(4),
+                    container(progress_bar(0.0..=100.0, win_rate).style(theme::ratio_bar))
+                        .width(120)
+                        .height(4),
+                ]
+                .spacing(2)
+            ]
+            .padding(padding::left(18).right(18))
+            .spacing(16)
+            .align_y(Alignment::Center),
         ]
-        .padding(padding::left(18).right(18))
-        .spacing(16)
-        .align_y(Alignment::Center),
-    ])
+        .push(if is_expanded {
+            container(chart.view()).padding(padding::bottom(8))
+        } else {
+            container(horizontal_space().width(0))
+        }),
+    )
     .style(theme::dark)
     .width(280)
     .into()
@@ -137,7 +155,7 @@ fn unranked_container<'a>(queue: game::Queue) -> Element<'a, Message> {
 
 #[derive(Debug, Clone)]
 pub enum Message {
-    Expand,
+    Expand(game::Queue),
 }
 
 #[derive(Debug, Clone)]
@@ -146,53 +164,87 @@ struct Stats {
     wins: u16,
     losses: u16,
     handle: image::Handle,
+    chart: Chart,
 }
 
 #[derive(Debug, Clone)]
 pub struct RankedOverview {
     solo_duo: Option<Stats>,
     flex: Option<Stats>,
+    /// The queue whose rank history is shown.
+    expanded: Option<game::Queue>,
 }
 
 impl RankedOverview {
-    pub fn from_profile(assets: &crate::assets::Assets, profile: &profile::Data) -> Self {
-        let solo_duo = profile
-            .leagues
-            .iter()
-            .find(|league| league.kind == league::Kind::SummonersRift(league::SummonersRift::Solo))
-            .filter(|league| league.tier.is_some())
-            .map(|league| Stats {
-                tier: league.tier.unwrap(),
-                wins: league.wins as u16,
-                losses: league.losses as u16,
-                handle: assets.emblem(&league.tier.unwrap()),
-            });
-
-        let flex = profile
-            .leagues
-            .iter()
-            .find(|league| league.kind == league::Kind::SummonersRift(league::SummonersRift::Flex))
-            .filter(|league| league.tier.is_some())
-            .map(|league| Stats {
-                tier: league.tier.unwrap(),
-                wins: league.wins as u16,
-                losses: league.losses as u16,
-                handle: assets.emblem(&league.tier.unwrap()),
-            });
-
-        Self { solo_duo, flex }
+    pub fn from_profile(
+        assets: &crate::assets::Assets,
+        profile: &profile::Data,
+        history: &History,
+    ) -> Self {
+        let stats = |kind: league::SummonersRift| {
+            let kind = league::Kind::SummonersRift(kind);
+
+            profile
+                .leagues
+                .iter()
+                .find(|league| league.kind == kind)
+                .filter(|league| league.tier.is_some())
+                .map(|league| Stats {
+                    tier: league.tier.unwrap(),
+                    wins: league.wins as u16,
+                    losses: league.losses as u16,
+                    handle: assets.emblem(&league.tier.unwrap()),
+                    chart: Chart::new(assets, history.snapshots(&kind)),
+                })
+        };
+
+        Self {
+            solo_duo: stats(league::SummonersRift::Solo),
+            flex: stats(league::SummonersRift::Flex),
+            expanded: None,
+        }
+    }
+
+    pub fn update(&mut self, message: Message) {
+        match message {
+            Message::Expand(queue) => {
+                self.expanded = if self.expanded == Some(queue) {
+                    None
+                } else {
+                    Some(queue)
+                };
+            }
+        }
     }
 
-    pub fn update(&mut self, _message: Message) {}
+    /// Redraws the charts of the ranked queues with the snapshots of `history`.
+    pub fn set_history(&mut self, assets: &crate::assets::Assets, history: &History) {
+        for (stats, kind) in [
+            (&mut self.solo_duo, league::SummonersRift::Solo),
+            (&mut self.flex, league::SummonersRift::Flex),
+        ] {
+            if let Some(stats) = stats {
+                stats.chart = Chart::new(
+                    assets,
+                    history.snapshots(&league::Kind::SummonersRift(kind)),
+                );
+            }
+        }
+    }
+
+    /// Keeps the rank history expanded, if it was before the overview was rebuilt.
+    pub fn restore(&mut self, previous: &RankedOverview) {
+        self.expanded = previous.expanded;
+    }
 
     pub fn view(&self) -> Element<'_, Message> {
+        let is_expanded = |queue| self.expanded == Some(queue);
+
         let solo_duo = match &self.solo_duo {
             Some(stats) => ranked_container(
                 game::Queue::RankedSolo,
-                stats.tier,
-                stats.wins,
-                stats.losses,
-                stats.handle.clone(),
+                stats,
+                is_expanded(game::Queue::RankedSolo),
             ),
             None => unranked_container(game::Queue::RankedSolo),
         };
@@ -200,10 +252,8 @@ impl RankedOverview {
         let flex = match &self.flex {
             Some(stats) => ranked_container(
                 game::Queue::RankedFlex,
-                stats.tier,
-                stats.wins,
-                stats.losses,
-                stats.handle.clone(),
+                stats,
+                is_expanded(game::Queue::RankedFlex),
             ),
             None => unranked_container(game::Queue::RankedFlex),
         };
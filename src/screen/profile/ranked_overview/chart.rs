@@ -0,0 +1,293 @@
+//! A chart of the rank of a summoner over time, in a ranked queue.
+use crate::core::summoner::{Tier, history};
+use crate::theme;
+
+use iced::mouse;
+use iced::widget::canvas::{self, Frame, Geometry, Path, Stroke, Text};
+use iced::widget::{column, container, image, row};
+use iced::{Element, Length, Pixels, Point, Rectangle, Renderer, Size, Theme};
+
+const HEIGHT: f32 = 120.0;
+const PADDING: f32 = 8.0;
+const TOOLTIP: Size = Size::new(112.0, 34.0);
+const MILLISECONDS_PER_DAY: i64 = 86_400_000;
+
+/// The start of every tier on the ladder, Master and above share a single band.
+const BANDS: [u32; 8] = [
+    0,
+    Tier::SPAN,
+    2 * Tier::SPAN,
+    3 * Tier::SPAN,
+    4 * Tier::SPAN,
+    5 * Tier::SPAN,
+    6 * Tier::SPAN,
+    7 * Tier::SPAN,
+];
+
+#[derive(Debug, Clone, Copy)]
+struct Day {
+    /// Days since the Unix epoch.
+    day: i64,
+    tier: Tier,
+}
+
+#[derive(Debug, Clone)]
+struct Band {
+    start: u32,
+    end: u32,
+    emblem: image::Handle,
+}
+
+#[derive(Debug, Clone)]
+pub struct Chart {
+    days: Vec<Day>,
+    /// The tiers crossed by the chart, from the lowest.
+    bands: Vec<Band>,
+}
+
+impl Chart {
+    /// Keeps the last rank of each day of the `snapshots`.
+    pub fn new<'a>(
+        assets: &crate::Assets,
+        snapshots: impl Iterator<Item = &'a history::Snapshot>,
+    ) -> Self {
+        let mut days: Vec<Day> = Vec::new();
+
+        for snapshot in snapshots {
+            let Some(tier) = snapshot.league.tier else {
+                continue;
+            };
+            let day = Day {
+                day: snapshot.time.div_euclid(MILLISECONDS_PER_DAY),
+                tier,
+            };
+
+            match days.last_mut() {
+                Some(last) if last.day == day.day => *last = day,
+                _ => days.push(day),
+            }
+        }
+
+        let (lowest, highest) = days
+            .iter()
+            .map(|day| day.tier.position())
+            .fold((u32::MAX, 0), |(lowest, highest), position| {
+                (lowest.min(position), highest.max(position))
+            });
+
+        let bands = BANDS
+            .iter()
+            .enumerate()
+            .filter_map(|(index, &start)| {
+                // The apex band grows with the points of the summoner
+                let end = BANDS
+                    .get(index + 1)
+                    .copied()
+                    .unwrap_or(highest.max(start) + 100);
+
+                (start <= highest && end > lowest).then(|| Band {
+                    start,
+                    end,
+                    emblem: assets.emblem(&Tier::from_position(start)),
+                })
+            })
+            .collect();
+
+        Self { days, bands }
+    }
+
+    pub fn view<'a, Message: 'a>(&'a self) -> Element<'a, Message> {
+        if self.days.len() < 2 {
+            return container(
+                iced::widget::text("Not enough history yet, the rank is recorded on every update")
+                    .size(11)
+                    .style(theme::text),
+            )
+            .padding(PADDING)
+            .into();
+        }
+
+        // The emblems line up with the bands, as both span the height of the chart
+        let emblems = column(self.bands.iter().rev().map(|band| {
+            container(image(band.emblem.clone()).width(20.0).height(20.0))
+                .center_y(Length::FillPortion((band.end - band.start) as u16))
+                .into()
+        }))
+        .height(HEIGHT);
+
+        row![
+            emblems,
+            iced::widget::canvas(self)
+                .width(Length::Fill)
+                .height(HEIGHT)
+        ]
+        .spacing(4)
+        .padding([0.0, PADDING])
+        .into()
+    }
+
+    fn range(&self) -> (u32, u32) {
+        let start = self.bands.first().map_or(0, |band| band.start);
+        let end = self.bands.last().map_or(Tier::SPAN, |band| band.end);
+
+        (start, end)
+    }
+
+    /// The index of the day closest to `x`, relative to the canvas.
+    fn day_at(&self, x: f32, width: f32) -> Option<usize> {
+        let first = self.days.first()?.day;
+        let last = self.days.last()?.day;
+        let day = first as f32 + x / width * (last - first).max(1) as f32;
+
+        self.days
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| {
+                (a.day as f32 - day)
+                    .abs()
+                    .total_cmp(&(b.day as f32 - day).abs())
+            })
+            .map(|(index, _)| index)
+    }
+}
+
+impl<Message> canvas::Program<Message> for Chart {
+    /// The hovered day.
+    type State = Option<usize>;
+
+    fn update(
+        &self,
+        hovered: &mut Self::State,
+        event: &canvas::Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Option<canvas::Action<Message>> {
+        let canvas::Event::Mouse(_) = event else {
+            return None;
+        };
+
+        let day = cursor
+            .position_in(bounds)
+            .and_then(|position| self.day_at(position.x, bounds.width));
+
+        if *hovered == day {
+            return None;
+        }
+
+        *hovered = day;
+
+        Some(canvas::Action::request_redraw())
+    }
+
+    fn draw(
+        &self,
+        hovered: &Self::State,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let palette = theme.extended_palette();
+        let text_color = palette.background.base.text.scale_alpha(0.8);
+        let line_color = palette.primary.base.color;
+
+        let mut frame = Frame::new(renderer, bounds.size());
+        let (start, end) = self.range();
+        let first = self.days.first().map_or(0, |day| day.day);
+        let last = self.days.last().map_or(0, |day| day.day);
+
+        let x = |day: i64| (day - first) as f32 / (last - first).max(1) as f32 * bounds.width;
+        let y = |position: u32| {
+            (1.0 - (position.saturating_sub(start)) as f32 / (end - start) as f32) * bounds.height
+        };
+
+        // Alternate the background of the tiers
+        for (index, band) in self.bands.iter().enumerate() {
+            let top = y(band.end);
+            let color = if index % 2 == 0 {
+                palette.background.weak.color
+            } else {
+                palette.background.strong.color
+            };
+
+            frame.fill_rectangle(
+                Point::new(0.0, top),
+                Size::new(bounds.width, y(band.start) - top),
+                color.scale_alpha(0.5),
+            );
+        }
+
+        let line = Path::new(|builder| {
+            for (index, day) in self.days.iter().enumerate() {
+                let point = Point::new(x(day.day), y(day.tier.position()));
+
+                if index == 0 {
+                    builder.move_to(point);
+                } else {
+                    builder.line_to(point);
+                }
+            }
+        });
+
+        frame.stroke(
+            &line,
+            Stroke::default().with_color(line_color).with_width(2.0),
+        );
+
+        if let Some(day) = hovered.and_then(|index| self.days.get(index)) {
+            let point = Point::new(x(day.day), y(day.tier.position()));
+
+            frame.fill(&Path::circle(point, 3.0), line_color);
+
+            // Keep the tooltip inside the chart, on the left of the point near the end
+            let left = if point.x + TOOLTIP.width + PADDING > bounds.width {
+                point.x - TOOLTIP.width - PADDING
+            } else {
+                point.x + PADDING
+            };
+            let tooltip = Path::rectangle(Point::new(left, PADDING / 2.0), TOOLTIP);
+
+            frame.fill(&tooltip, palette.background.base.color);
+            frame.stroke(
+                &tooltip,
+                Stroke::default()
+                    .with_color(palette.background.strong.color)
+                    .with_width(1.0),
+            );
+
+            let date = time::OffsetDateTime::from_unix_timestamp(day.day * 86_400)
+                .map(|time| time.date())
+                .unwrap_or(time::Date::MIN);
+
+            frame.fill_text(Text {
+                content: format!("{} {}", date.month(), date.day()),
+                position: Point::new(left + 6.0, PADDING / 2.0 + 4.0),
+                color: text_color,
+                size: Pixels(10.0),
+                ..Text::default()
+            });
+            frame.fill_text(Text {
+                content: day.tier.to_string(),
+                position: Point::new(left + 6.0, PADDING / 2.0 + 18.0),
+                color: text_color,
+                size: Pixels(10.0),
+                ..Text::default()
+            });
+        }
+
+        vec![frame.into_geometry()]
+    }
+
+    fn mouse_interaction(
+        &self,
+        _hovered: &Self::State,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> mouse::Interaction {
+        if cursor.is_over(bounds) {
+            mouse::Interaction::Crosshair
+        } else {
+            mouse::Interaction::default()
+        }
+    }
+}
@@ -44,6 +44,8 @@ pub struct Champion {
     pub kills: usize,
     pub deaths: usize,
     pub assists: usize,
+    /// The share of the games where the champion was banned, in percent.
+    pub ban_rate: f32,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -69,6 +71,7 @@ pub struct Summary {
 
 impl Summary {
     pub fn from_games(assets: &crate::Assets, games: &[profile::Game]) -> Summary {
+        let bans = games.iter().flat_map(profile::Game::bans).counts();
         let games = games.iter().map(profile::Game::player).collect_vec();
         let total = games.len();
         let wins = games.iter().filter(|game| game.result.won()).count();
@@ -111,6 +114,8 @@ impl Summary {
                 kills: stats.kills,
                 deaths: stats.deaths,
                 assists: stats.assists,
+                ban_rate: bans.get(&champion).copied().unwrap_or_default() as f32 * 100.0
+                    / total as f32,
             })
             .collect_vec();
 
@@ -276,7 +281,13 @@ impl Summary {
                                     .size(10)
                                     .style(theme::text)
                             )
-                            .padding(padding::top(2).left(2))
+                            .padding(padding::top(2).left(2)),
+                            container(
+                                text!("{:.0}% ban", champion.ban_rate)
+                                    .size(10)
+                                    .style(theme::text)
+                            )
+                            .padding(padding::top(2))
                         ]
                         .spacing(2)
                         .align_y(Alignment::Center),
@@ -137,6 +137,17 @@ pub mod icon {
         icon(HANDLE.clone())
     }
 
+    pub fn summoners_rift<'a>() -> svg::Svg<'a> {
+        static HANDLE: LazyLock<svg::Handle> = LazyLock::new(|| {
+            svg::Handle::from_memory(include_bytes!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/img/map/summoners_rift.svg"
+            )))
+        });
+
+        icon(HANDLE.clone())
+    }
+
     pub fn role<'a>(role: game::Role) -> svg::Svg<'a> {
         static BOTTOM: LazyLock<svg::Handle> = LazyLock::new(|| {
             svg::Handle::from_memory(include_bytes!(concat!(
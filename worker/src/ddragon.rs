@@ -0,0 +1,54 @@
+use aery_core::assets::Image;
+
+const DEFAULT_URL: &str = "https://ddragon.leagueoflegends.com";
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("request to data dragon failed: {0}")]
+    Request(#[from] reqwest::Error),
+    #[error("no data dragon version available")]
+    MissingVersion,
+}
+
+#[derive(Debug, Clone)]
+pub struct DataDragon {
+    http: reqwest::Client,
+    url: String,
+    version: String,
+}
+
+impl DataDragon {
+    pub async fn new(url: Option<String>) -> Result<Self, Error> {
+        let http = reqwest::Client::new();
+        let url = url.unwrap_or_else(|| String::from(DEFAULT_URL));
+
+        let versions: Vec<String> = http
+            .get(format!("{url}/api/versions.json"))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        let version = versions.into_iter().next().ok_or(Error::MissingVersion)?;
+
+        tracing::info!("Using Data Dragon version {version}");
+
+        Ok(Self { http, url, version })
+    }
+
+    pub async fn profile_icon(&self, icon_id: i32) -> Result<Image, Error> {
+        let bytes = self
+            .http
+            .get(format!(
+                "{}/cdn/{}/img/profileicon/{icon_id}.png",
+                self.url, self.version
+            ))
+            .send()
+            .await?
+            .error_for_status()?
+            .bytes()
+            .await?;
+
+        Ok(Image::from(bytes))
+    }
+}
@@ -0,0 +1,348 @@
+mod ddragon;
+use ddragon::DataDragon;
+
+use aery_core as core;
+use core::account::RiotId;
+use core::assets::{Icon, index};
+use core::summoner;
+use core::{Account, ParseError, Region, RequestError, Summoner, game, live};
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Router, body::Bytes};
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error("invalid request: {0}")]
+    BadRequest(String),
+    #[error(transparent)]
+    Request(#[from] RequestError),
+    #[error(transparent)]
+    DataDragon(#[from] ddragon::Error),
+}
+
+impl From<game::RequestError> for Error {
+    fn from(error: game::RequestError) -> Self {
+        Self::Request(RequestError::from(error))
+    }
+}
+
+impl IntoResponse for Error {
+    fn into_response(self) -> Response {
+        let status = match &self {
+            Error::BadRequest(_) | Error::Request(RequestError::Parse(_)) => {
+                StatusCode::BAD_REQUEST
+            }
+            Error::Request(RequestError::NotFound) => StatusCode::NOT_FOUND,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        if status.is_server_error() {
+            tracing::error!("{self:?}");
+        }
+
+        (status, self.to_string()).into_response()
+    }
+}
+
+#[derive(Clone)]
+struct Worker {
+    client: core::Client,
+    ddragon: DataDragon,
+    assets: Bytes,
+    assets_version: String,
+    assets_index: Bytes,
+    icons: Bytes,
+    icon_locations: BTreeMap<Icon, index::Location>,
+    deltas: PathBuf,
+}
+
+#[tokio::main]
+async fn main() {
+    use tracing_subscriber::EnvFilter;
+    let env_filter = EnvFilter::builder()
+        .with_default_directive(tracing::Level::INFO.into())
+        .from_env()
+        .unwrap_or_default()
+        .add_directive("aery_worker=trace".parse().unwrap_or_default());
+
+    tracing_subscriber::fmt().with_env_filter(env_filter).init();
+
+    let key = std::env::var("RIOT_API_KEY").expect("`RIOT_API_KEY` must be set");
+    let client = match std::env::var("RIOT_API_URL") {
+        Ok(url) => core::Client::with_base_url(key, url),
+        Err(_) => core::Client::new(key),
+    };
+
+    let ddragon = DataDragon::new(std::env::var("DDRAGON_URL").ok())
+        .await
+        .expect("failed to reach Data Dragon");
+
+    let assets_path =
+        std::env::var("ASSETS_PATH").unwrap_or_else(|_| String::from("assets/latest.aery"));
+    let bundle = tokio::fs::read(&assets_path)
+        .await
+        .unwrap_or_else(|error| panic!("failed to read assets at `{assets_path}`: {error}"));
+    let bundle = core::Assets::decode(bundle)
+        .unwrap_or_else(|error| panic!("invalid assets at `{assets_path}`: {error}"));
+    let version = bundle.version.clone();
+    let encoded = Bytes::from(bundle.encode());
+    let (index, icons) = bundle.into_index();
+
+    tracing::info!("Serving assets version {version}");
+
+    let deltas = std::env::var("DELTAS_PATH").unwrap_or_else(|_| String::from("assets/deltas"));
+
+    let worker = Worker {
+        client,
+        ddragon,
+        assets: encoded,
+        assets_version: version,
+        assets_index: Bytes::from(index.encode()),
+        icons: Bytes::from(icons),
+        icon_locations: index.icons,
+        deltas: PathBuf::from(deltas),
+    };
+
+    let router = Router::new()
+        .route("/summoner/{region}/{riot_id}", get(summoner))
+        .route("/matches/{puuid}", get(matches))
+        .route("/timeline/{id}", get(timeline))
+        .route("/ranks/{region}", get(ranks))
+        .route("/live/{region}/{puuid}", get(live_game))
+        .route("/assets/version", get(assets_version))
+        .route("/assets/{version}", get(assets))
+        .route("/assets/{version}/index", get(assets_index))
+        .route("/assets/{version}/icons/{kind}/{id}", get(icon))
+        .route("/assets/delta/{version}", get(assets_delta))
+        .with_state(Arc::new(worker));
+
+    let address = std::env::var("ADDRESS").unwrap_or_else(|_| String::from("127.0.0.1:8787"));
+    let listener = tokio::net::TcpListener::bind(&address)
+        .await
+        .unwrap_or_else(|error| panic!("failed to bind `{address}`: {error}"));
+
+    tracing::info!("Listening on {address}");
+    axum::serve(listener, router).await.unwrap();
+}
+
+impl Worker {
+    /// Fails unless the assets `version` is the one being served, `latest` always is.
+    fn serves(&self, version: &str) -> Result<(), Error> {
+        if version != "latest" && version != self.assets_version {
+            return Err(Error::Request(RequestError::NotFound));
+        }
+
+        Ok(())
+    }
+}
+
+fn region(region: &str) -> Result<Region, Error> {
+    region
+        .parse()
+        .map_err(|error: ParseError| Error::BadRequest(error.to_string()))
+}
+
+/// The region of a `region` query parameter, which is required.
+fn query_region(region: Option<String>) -> Result<Region, Error> {
+    let region = region.ok_or_else(|| Error::BadRequest(String::from("missing `region`")))?;
+
+    self::region(&region)
+}
+
+async fn summoner(
+    State(worker): State<Arc<Worker>>,
+    Path((region, riot_id)): Path<(String, String)>,
+) -> Result<Vec<u8>, Error> {
+    let region = self::region(&region)?;
+    let riot_id = riot_id
+        .rsplit_once('-')
+        .map(|(name, tagline)| RiotId::new(name, tagline))
+        .ok_or_else(|| Error::BadRequest(format!("invalid riot id `{riot_id}`")))?;
+
+    tracing::info!("Fetching summoner `{riot_id}` ({region})");
+
+    let account = Account::fetch(&worker.client, region, &riot_id).await?;
+    let summoner = Summoner::fetch(&worker.client, region, account).await?;
+    let leagues = summoner::League::fetch(&worker.client, region, summoner.puuid()).await?;
+    let games = game::Map::fetch(&worker.client, region, summoner.puuid(), None, None).await?;
+    let icon = worker.ddragon.profile_icon(summoner.icon_id).await?;
+
+    let data = summoner::Data {
+        icon,
+        summoner,
+        leagues,
+        games: games.iter().map(|(_, game)| game.clone()).collect(),
+    };
+
+    Ok(data.encode())
+}
+
+#[derive(serde::Deserialize)]
+struct MatchesQuery {
+    start_time: Option<i64>,
+    end_time: Option<i64>,
+    region: Option<String>,
+}
+
+async fn matches(
+    State(worker): State<Arc<Worker>>,
+    Path(puuid): Path<String>,
+    Query(query): Query<MatchesQuery>,
+) -> Result<Vec<u8>, Error> {
+    let region = self::query_region(query.region)?;
+
+    tracing::info!("Fetching matches of `{puuid}` ({region})");
+
+    let games = game::Map::fetch(
+        &worker.client,
+        region,
+        &puuid,
+        query.start_time,
+        query.end_time,
+    )
+    .await?;
+
+    Ok(games.encode())
+}
+
+#[derive(serde::Deserialize)]
+struct TimelineQuery {
+    region: Option<String>,
+}
+
+async fn timeline(
+    State(worker): State<Arc<Worker>>,
+    Path(id): Path<String>,
+    Query(query): Query<TimelineQuery>,
+) -> Result<Vec<u8>, Error> {
+    let region = self::query_region(query.region)?;
+    let id = game::Id::try_from(id.clone())
+        .map_err(|_| Error::BadRequest(format!("invalid game id `{id}`")))?;
+
+    tracing::info!("Fetching timeline of `{}` ({region})", id.as_ref());
+
+    let timeline = game::Timeline::fetch(&worker.client, region, &id).await?;
+
+    Ok(timeline.encode())
+}
+
+/// Serves the game `puuid` is playing, or `404` if they are not in game.
+async fn live_game(
+    State(worker): State<Arc<Worker>>,
+    Path((region, puuid)): Path<(String, String)>,
+) -> Result<Vec<u8>, Error> {
+    let region = self::region(&region)?;
+
+    tracing::info!("Fetching live game of `{puuid}` ({region})");
+
+    let game = live::Game::fetch(&worker.client, region, &puuid)
+        .await?
+        .ok_or(Error::Request(RequestError::NotFound))?;
+
+    Ok(game.encode())
+}
+
+#[derive(serde::Deserialize)]
+struct RanksQuery {
+    /// Comma separated puuids.
+    puuids: String,
+}
+
+async fn ranks(
+    State(worker): State<Arc<Worker>>,
+    Path(region): Path<String>,
+    Query(query): Query<RanksQuery>,
+) -> Result<Vec<u8>, Error> {
+    let region = self::region(&region)?;
+    let puuids = query
+        .puuids
+        .split(',')
+        .filter(|puuid| !puuid.is_empty())
+        .map(String::from)
+        .collect::<Vec<_>>();
+
+    if puuids.len() > summoner::ranks::BATCH {
+        return Err(Error::BadRequest(format!(
+            "at most {} puuids can be requested at once",
+            summoner::ranks::BATCH
+        )));
+    }
+
+    tracing::info!("Fetching leagues of {} summoners ({region})", puuids.len());
+
+    let ranks = summoner::Ranks::fetch(&worker.client, region, &puuids).await;
+
+    Ok(ranks.encode())
+}
+
+async fn assets(
+    State(worker): State<Arc<Worker>>,
+    Path(version): Path<String>,
+) -> Result<Bytes, Error> {
+    worker.serves(&version)?;
+
+    Ok(worker.assets.clone())
+}
+
+async fn assets_index(
+    State(worker): State<Arc<Worker>>,
+    Path(version): Path<String>,
+) -> Result<Bytes, Error> {
+    worker.serves(&version)?;
+
+    Ok(worker.assets_index.clone())
+}
+
+async fn icon(
+    State(worker): State<Arc<Worker>>,
+    Path((version, kind, id)): Path<(String, String, u32)>,
+) -> Result<Bytes, Error> {
+    worker.serves(&version)?;
+
+    let location = Icon::from_parts(&kind, id)
+        .and_then(|icon| worker.icon_locations.get(&icon))
+        .ok_or(Error::Request(RequestError::NotFound))?;
+    let start = location.offset as usize;
+
+    Ok(worker.icons.slice(start..start + location.length as usize))
+}
+
+async fn assets_version(State(worker): State<Arc<Worker>>) -> String {
+    worker.assets_version.clone()
+}
+
+/// Serves the delta from `version` to the current assets, read from `{DELTAS_PATH}/{version}.aery`.
+async fn assets_delta(
+    State(worker): State<Arc<Worker>>,
+    Path(version): Path<String>,
+) -> Result<Vec<u8>, Error> {
+    if version.is_empty() || !version.chars().all(|c| c.is_ascii_digit() || c == '.') {
+        return Err(Error::BadRequest(format!("invalid version `{version}`")));
+    }
+
+    let path = worker.deltas.join(format!("{version}.aery"));
+    let bytes = tokio::fs::read(&path)
+        .await
+        .map_err(|_| Error::Request(RequestError::NotFound))?;
+
+    match core::assets::Delta::decode(&bytes) {
+        Ok(delta) if delta.to == worker.assets_version => Ok(bytes),
+        Ok(delta) => {
+            tracing::warn!(
+                "Ignoring outdated delta `{}` ({} to {})",
+                path.display(),
+                delta.from,
+                delta.to
+            );
+            Err(Error::Request(RequestError::NotFound))
+        }
+        Err(error) => Err(Error::Request(RequestError::from(error))),
+    }
+}